@@ -0,0 +1,37 @@
+//! Generates the `registry!` of every `day_N` dependency, so that a day is added to the binary by
+//! adding its crate to Cargo.toml, which `aqa_aoc` does when it creates the crate.
+
+use std::path::Path;
+use std::{env, fs};
+
+fn main() {
+	println!("cargo:rerun-if-changed=Cargo.toml");
+	let manifest = fs::read_to_string("Cargo.toml").expect("failed to read Cargo.toml");
+
+	let mut days = Vec::new();
+	let mut in_dependencies = false;
+	for line in manifest.lines().map(str::trim) {
+		if line.starts_with('[') {
+			in_dependencies = line == "[dependencies]";
+			continue;
+		}
+		let name = line.split(['=', ' ']).next().unwrap_or_default();
+		match name.strip_prefix("day_").map(str::parse::<u32>) {
+			Some(Ok(day)) if in_dependencies => days.push(day),
+			_ => (),
+		}
+	}
+	days.sort_unstable();
+
+	let crates = days
+		.iter()
+		.map(|day| format!("day_{}", day))
+		.collect::<Vec<_>>()
+		.join(", ");
+	let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+	fs::write(
+		Path::new(&out_dir).join("registry.rs"),
+		format!("aoc_common::registry![{}]\n", crates),
+	)
+	.expect("failed to write the registry");
+}
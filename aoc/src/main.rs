@@ -0,0 +1,13 @@
+//! Runs the solution of any day: `aoc 3 7` runs days 3 and 7, `aoc` all of them.
+
+use std::path::Path;
+
+fn main() -> anyhow::Result<()> {
+	// The crate is a member of the workspace, next to the days
+	let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+	aoc_common::registry::main(
+		&workspace_root,
+		// Every `day_N` dependency, see build.rs
+		&include!(concat!(env!("OUT_DIR"), "/registry.rs")),
+	)
+}
@@ -0,0 +1,439 @@
+//! Grids of the 2D puzzles: [Grid] for a map given in the input, [SparseGrid] for a world that
+//! grows as the puzzle goes.
+
+use anyhow::bail;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+/// `(x, y)`, with `y` growing downwards like the lines of the input.
+pub type Pos = (i64, i64);
+
+pub const UP: Pos = (0, -1);
+pub const DOWN: Pos = (0, 1);
+pub const LEFT: Pos = (-1, 0);
+pub const RIGHT: Pos = (1, 0);
+
+pub const DIRECTIONS_4: [Pos; 4] = [UP, RIGHT, DOWN, LEFT];
+pub const DIRECTIONS_8: [Pos; 8] = [UP, (1, -1), RIGHT, (1, 1), DOWN, (-1, 1), LEFT, (-1, -1)];
+
+pub fn step((x, y): Pos, (dx, dy): Pos) -> Pos {
+	(x + dx, y + dy)
+}
+
+/// A rectangle of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+	width: usize,
+	height: usize,
+	cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+	pub fn new(width: usize, height: usize, cell: T) -> Grid<T> {
+		Grid {
+			width,
+			height,
+			cells: vec![cell; width * height],
+		}
+	}
+}
+
+impl<T> Grid<T> {
+	/// One row per line and one cell per character, every line must be as long as the first.
+	/// Blank lines around the grid are left out.
+	pub fn parse(
+		input: &str,
+		mut cell: impl FnMut(char) -> anyhow::Result<T>,
+	) -> anyhow::Result<Grid<T>> {
+		let mut width = None;
+		let mut height = 0;
+		let mut cells = Vec::new();
+		for (idx, line) in input.trim_matches(['\n', '\r']).lines().enumerate() {
+			let len = line.chars().count();
+			match width {
+				None => width = Some(len),
+				Some(width) if width != len => {
+					bail!("line {} is {} long, expected {}", idx + 1, len, width)
+				}
+				Some(_) => (),
+			}
+			for c in line.chars() {
+				cells.push(cell(c)?);
+			}
+			height += 1;
+		}
+
+		Ok(Grid {
+			width: width.unwrap_or(0),
+			height,
+			cells,
+		})
+	}
+
+	pub fn width(&self) -> usize {
+		self.width
+	}
+
+	pub fn height(&self) -> usize {
+		self.height
+	}
+
+	pub fn contains(&self, (x, y): Pos) -> bool {
+		(0..self.width as i64).contains(&x) && (0..self.height as i64).contains(&y)
+	}
+
+	fn idx(&self, pos: Pos) -> Option<usize> {
+		self.contains(pos)
+			.then(|| pos.1 as usize * self.width + pos.0 as usize)
+	}
+
+	/// The cell at `pos`, `None` outside the grid.
+	pub fn get(&self, pos: Pos) -> Option<&T> {
+		self.idx(pos).map(|idx| &self.cells[idx])
+	}
+
+	pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+		self.idx(pos).map(|idx| &mut self.cells[idx])
+	}
+
+	/// Every position, row by row.
+	pub fn positions(&self) -> impl Iterator<Item = Pos> {
+		let (width, height) = (self.width as i64, self.height as i64);
+		(0..height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+		self.positions().zip(&self.cells)
+	}
+
+	/// The cells up, right, down and left of `pos` that are in the grid.
+	pub fn neighbours_4(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+		self.around(pos, &DIRECTIONS_4)
+	}
+
+	/// [Grid::neighbours_4] and the diagonals, clockwise from up.
+	pub fn neighbours_8(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+		self.around(pos, &DIRECTIONS_8)
+	}
+
+	fn around<'a>(
+		&'a self,
+		pos: Pos,
+		directions: &'static [Pos],
+	) -> impl Iterator<Item = (Pos, &'a T)> + 'a {
+		directions.iter().filter_map(move |direction| {
+			let next = step(pos, *direction);
+			self.get(next).map(|cell| (next, cell))
+		})
+	}
+
+	/// The cells from `from` in `direction` up to the edge, leaving out `from`. No cells for a zero
+	/// `direction`, which never reaches the edge.
+	pub fn ray(&self, from: Pos, direction: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+		let (dx, dy) = direction;
+		(1..).map_while(move |distance| {
+			if direction == (0, 0) {
+				return None;
+			}
+			let pos = step(from, (dx * distance, dy * distance));
+			self.get(pos).map(|cell| (pos, cell))
+		})
+	}
+
+	pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+		// `chunks` panics on 0, a grid without columns has no rows either
+		self.cells.chunks(self.width.max(1))
+	}
+
+	pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+		(0..self.width).map(move |x| self.cells.iter().skip(x).step_by(self.width))
+	}
+
+	/// Position of the first `cell`, row by row. For the one-off markers like `S`.
+	pub fn find(&self, cell: &T) -> Option<Pos>
+	where
+		T: PartialEq,
+	{
+		self.iter()
+			.find(|(_, other)| *other == cell)
+			.map(|(pos, _)| pos)
+	}
+
+	pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+		Grid {
+			width: self.width,
+			height: self.height,
+			cells: self.cells.iter().map(f).collect(),
+		}
+	}
+}
+
+impl<T> Index<Pos> for Grid<T> {
+	type Output = T;
+
+	fn index(&self, pos: Pos) -> &T {
+		let (width, height) = (self.width, self.height);
+		self.get(pos)
+			.unwrap_or_else(|| panic!("{:?} is outside of the {}x{} grid", pos, width, height))
+	}
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+	fn index_mut(&mut self, pos: Pos) -> &mut T {
+		let (width, height) = (self.width, self.height);
+		self.get_mut(pos)
+			.unwrap_or_else(|| panic!("{:?} is outside of the {}x{} grid", pos, width, height))
+	}
+}
+
+/// The grid as it was parsed, one line per row.
+impl<T: Display> Display for Grid<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for row in self.rows() {
+			for cell in row {
+				write!(f, "{}", cell)?;
+			}
+			writeln!(f)?;
+		}
+		Ok(())
+	}
+}
+
+/// Cells at any position, the ones never set are empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+	cells: HashMap<Pos, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+	fn default() -> Self {
+		SparseGrid {
+			cells: HashMap::new(),
+		}
+	}
+}
+
+impl<T> SparseGrid<T> {
+	pub fn new() -> SparseGrid<T> {
+		SparseGrid::default()
+	}
+
+	/// Like [Grid::parse], leaving the cells for which `cell` returns `None` empty. The lines
+	/// don't have to be as long as each other.
+	pub fn parse(
+		input: &str,
+		mut cell: impl FnMut(char) -> anyhow::Result<Option<T>>,
+	) -> anyhow::Result<SparseGrid<T>> {
+		let mut grid = SparseGrid::new();
+		for (y, line) in input.trim_matches(['\n', '\r']).lines().enumerate() {
+			for (x, c) in line.chars().enumerate() {
+				if let Some(cell) = cell(c)? {
+					grid.insert((x as i64, y as i64), cell);
+				}
+			}
+		}
+		Ok(grid)
+	}
+
+	pub fn len(&self) -> usize {
+		self.cells.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.cells.is_empty()
+	}
+
+	pub fn get(&self, pos: Pos) -> Option<&T> {
+		self.cells.get(&pos)
+	}
+
+	pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+		self.cells.get_mut(&pos)
+	}
+
+	pub fn contains(&self, pos: Pos) -> bool {
+		self.cells.contains_key(&pos)
+	}
+
+	/// Sets the cell, returning what was there.
+	pub fn insert(&mut self, pos: Pos, cell: T) -> Option<T> {
+		self.cells.insert(pos, cell)
+	}
+
+	pub fn remove(&mut self, pos: Pos) -> Option<T> {
+		self.cells.remove(&pos)
+	}
+
+	/// The cells that are set, in no particular order.
+	pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+		self.cells.iter().map(|(pos, cell)| (*pos, cell))
+	}
+
+	/// The smallest and largest `x` and `y` of the cells that are set, `None` when there are
+	/// none.
+	pub fn bounds(&self) -> Option<(Pos, Pos)> {
+		self.cells.keys().fold(None, |bounds, &(x, y)| {
+			Some(match bounds {
+				None => ((x, y), (x, y)),
+				Some(((min_x, min_y), (max_x, max_y))) => {
+					((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y)))
+				}
+			})
+		})
+	}
+
+	/// The cells that are set up, right, down and left of `pos`.
+	pub fn neighbours_4(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+		self.around(pos, &DIRECTIONS_4)
+	}
+
+	/// [SparseGrid::neighbours_4] and the diagonals, clockwise from up.
+	pub fn neighbours_8(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+		self.around(pos, &DIRECTIONS_8)
+	}
+
+	fn around<'a>(
+		&'a self,
+		pos: Pos,
+		directions: &'static [Pos],
+	) -> impl Iterator<Item = (Pos, &'a T)> + 'a {
+		directions.iter().filter_map(move |direction| {
+			let next = step(pos, *direction);
+			self.get(next).map(|cell| (next, cell))
+		})
+	}
+
+	/// The first cell that is set from `from` in `direction`, leaving out `from`. Gives up past
+	/// [SparseGrid::bounds], there's nothing there.
+	pub fn ray(&self, from: Pos, direction: Pos) -> Option<(Pos, &T)> {
+		let ((min_x, min_y), (max_x, max_y)) = self.bounds()?;
+		if direction == (0, 0) {
+			return None;
+		}
+
+		// Outside of the bounds on a side the ray is heading away from
+		let past_bounds = |(x, y): Pos| {
+			let (dx, dy) = direction;
+			(x < min_x && dx <= 0)
+				|| (x > max_x && dx >= 0)
+				|| (y < min_y && dy <= 0)
+				|| (y > max_y && dy >= 0)
+		};
+		let mut pos = step(from, direction);
+		while !past_bounds(pos) {
+			if let Some(cell) = self.get(pos) {
+				return Some((pos, cell));
+			}
+			pos = step(pos, direction);
+		}
+		None
+	}
+
+	/// The dense grid of [SparseGrid::bounds], with `empty` where no cell is set.
+	pub fn to_grid(&self, empty: T) -> Grid<T>
+	where
+		T: Clone,
+	{
+		let Some(((min_x, min_y), (max_x, max_y))) = self.bounds() else {
+			return Grid::new(0, 0, empty);
+		};
+		let mut grid = Grid::new(
+			(max_x - min_x + 1) as usize,
+			(max_y - min_y + 1) as usize,
+			empty,
+		);
+		for ((x, y), cell) in self.iter() {
+			grid[(x - min_x, y - min_y)] = cell.clone();
+		}
+		grid
+	}
+}
+
+/// The cells within [SparseGrid::bounds], with `.` where no cell is set.
+impl<T: Display> Display for SparseGrid<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let Some(((min_x, min_y), (max_x, max_y))) = self.bounds() else {
+			return Ok(());
+		};
+		for y in min_y..=max_y {
+			for x in min_x..=max_x {
+				match self.get((x, y)) {
+					Some(cell) => write!(f, "{}", cell)?,
+					None => write!(f, ".")?,
+				}
+			}
+			writeln!(f)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Grid, SparseGrid, DOWN, LEFT, RIGHT, UP};
+
+	const MAP: &str = "Sab\nd#c\nefE\n";
+
+	#[test]
+	fn grid() {
+		let grid = Grid::parse(MAP, Ok).unwrap();
+		assert_eq!((grid.width(), grid.height()), (3, 3));
+		assert_eq!(grid.to_string(), MAP);
+		assert_eq!(grid.find(&'S'), Some((0, 0)));
+		assert_eq!(grid.find(&'E'), Some((2, 2)));
+		assert_eq!(grid.find(&'x'), None);
+		assert_eq!(grid.get((1, 1)), Some(&'#'));
+		assert_eq!(grid.get((3, 0)), None);
+		assert_eq!(grid.get((0, -1)), None);
+		assert_eq!(grid[(2, 1)], 'c');
+
+		let cells = |cells: Vec<(_, &char)>| cells.into_iter().map(|(_, c)| *c).collect::<String>();
+		assert_eq!(cells(grid.neighbours_4((0, 0)).collect()), "ad");
+		assert_eq!(cells(grid.neighbours_4((1, 1)).collect()), "acfd");
+		assert_eq!(cells(grid.neighbours_8((1, 1)).collect()), "abcEfedS");
+		assert_eq!(cells(grid.ray((0, 2), UP).collect()), "dS");
+		assert_eq!(cells(grid.ray((0, 2), RIGHT).collect()), "fE");
+		assert_eq!(cells(grid.ray((0, 2), DOWN).collect()), "");
+		assert_eq!(grid.ray((0, 0), (1, 1)).last(), Some(((2, 2), &'E')));
+		assert_eq!(grid.ray((1, 1), (0, 0)).count(), 0);
+
+		let rows = grid.rows().map(|row| row.iter().collect::<String>());
+		assert_eq!(rows.collect::<Vec<_>>(), vec!["Sab", "d#c", "efE"]);
+		let columns = grid.columns().map(|column| column.collect::<String>());
+		assert_eq!(columns.collect::<Vec<_>>(), vec!["Sde", "a#f", "bcE"]);
+
+		let mut heights = grid.map(|c| *c as u8);
+		heights[(0, 0)] = b'a';
+		assert_eq!(heights.get((0, 0)), Some(&b'a'));
+
+		assert_eq!(
+			Grid::parse("ab\nc\n", Ok).unwrap_err().to_string(),
+			"line 2 is 1 long, expected 2"
+		);
+		assert!(Grid::parse("12\n3x\n", |c| c
+			.to_digit(10)
+			.ok_or_else(|| anyhow::anyhow!("not a digit: {}", c)))
+		.is_err());
+	}
+
+	#[test]
+	fn sparse_grid() {
+		let mut grid = SparseGrid::parse("#..\n..#\n", |c| Ok((c == '#').then_some('#'))).unwrap();
+		assert_eq!(grid.len(), 2);
+		assert_eq!(grid.bounds(), Some(((0, 0), (2, 1))));
+		grid.insert((-1, 3), 'o');
+		assert_eq!(grid.bounds(), Some(((-1, 0), (2, 3))));
+		assert_eq!(grid.to_string(), ".#..\n...#\n....\no...\n");
+		assert_eq!(grid.to_grid('.').to_string(), grid.to_string());
+		assert_eq!(grid.neighbours_8((0, 1)).count(), 1);
+
+		assert_eq!(grid.ray((2, -5), DOWN), Some(((2, 1), &'#')));
+		assert_eq!(grid.ray((2, 1), DOWN), None);
+		assert_eq!(grid.ray((10, 3), LEFT), Some(((-1, 3), &'o')));
+		assert_eq!(grid.ray((10, 3), RIGHT), None);
+
+		assert_eq!(grid.remove((-1, 3)), Some('o'));
+		assert!(SparseGrid::<char>::new().to_string().is_empty());
+	}
+}
@@ -0,0 +1,173 @@
+//! Finding the input of a day from wherever the solution runs: `cargo run` in the workspace root or
+//! in the crate, an IDE, or the binary on its own.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Path of the input, or `-` for stdin. Used when no path is given on the command line.
+pub const INPUT_ENV: &str = "AOC_INPUT";
+
+pub const INPUT_FILE: &str = "input.txt";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+	Stdin,
+	File(PathBuf),
+}
+
+/// Where the input of a day is looked for. A path given on the command line or in [INPUT_ENV] is
+/// the only place looked at, otherwise `input.txt` in each crate directory and then `day_N/input.txt`
+/// in the working directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Loader {
+	day: u32,
+	arg: Option<String>,
+	env: Option<String>,
+	crate_dirs: Vec<PathBuf>,
+}
+
+impl Loader {
+	pub fn new(day: u32) -> Loader {
+		Loader {
+			day,
+			arg: None,
+			env: None,
+			crate_dirs: Vec::new(),
+		}
+	}
+
+	/// Loader for the `main` of a day crate: the first argument, then [INPUT_ENV], then the crate in
+	/// `manifest_dir`, which is `env!("CARGO_MANIFEST_DIR")` of the crate, and the one cargo sets at
+	/// run time.
+	pub fn from_env(day: u32, manifest_dir: &str) -> Loader {
+		let mut loader = Loader::new(day)
+			.arg(std::env::args().nth(1))
+			.env(std::env::var(INPUT_ENV).ok())
+			.crate_dir(manifest_dir);
+		if let Ok(runtime_dir) = std::env::var("CARGO_MANIFEST_DIR") {
+			loader = loader.crate_dir(runtime_dir);
+		}
+		loader
+	}
+
+	/// Path or `-` from the command line.
+	pub fn arg(mut self, arg: Option<String>) -> Loader {
+		self.arg = arg.filter(|arg| !arg.is_empty());
+		self
+	}
+
+	/// Value of [INPUT_ENV].
+	pub fn env(mut self, env: Option<String>) -> Loader {
+		self.env = env.filter(|env| !env.is_empty());
+		self
+	}
+
+	/// Directory of the day crate, with `input.txt` in it.
+	pub fn crate_dir(mut self, dir: impl Into<PathBuf>) -> Loader {
+		let dir = dir.into();
+		if !self.crate_dirs.contains(&dir) {
+			self.crate_dirs.push(dir);
+		}
+		self
+	}
+
+	/// The places to look at, in order.
+	pub fn sources(&self) -> Vec<Source> {
+		if let Some(explicit) = self.arg.as_deref().or(self.env.as_deref()) {
+			return vec![match explicit {
+				"-" => Source::Stdin,
+				path => Source::File(PathBuf::from(path)),
+			}];
+		}
+
+		let mut sources = self
+			.crate_dirs
+			.iter()
+			.map(|dir| Source::File(dir.join(INPUT_FILE)))
+			.collect::<Vec<_>>();
+		sources.push(Source::File(
+			Path::new(&format!("day_{}", self.day)).join(INPUT_FILE),
+		));
+		sources
+	}
+
+	/// The first input found. The error lists every place that was tried, and why it failed.
+	pub fn load(&self) -> anyhow::Result<String> {
+		let mut tried = Vec::new();
+		for source in self.sources() {
+			let read = match &source {
+				Source::Stdin => {
+					let mut input = String::new();
+					std::io::stdin().read_to_string(&mut input).map(|_| input)
+				}
+				Source::File(path) => std::fs::read_to_string(path),
+			};
+			match read {
+				Ok(input) => return Ok(input),
+				Err(err) => tried.push(match source {
+					Source::Stdin => format!("\n  stdin: {}", err),
+					Source::File(path) => format!("\n  {}: {}", path.display(), err),
+				}),
+			}
+		}
+
+		Err(anyhow::anyhow!(
+			"failed to read the input of day {}, tried:{}\nPass the path of the input as the first argument, `-` for stdin, or set {}",
+			self.day,
+			tried.concat(),
+			INPUT_ENV
+		))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Loader, Source};
+	use std::fs;
+	use std::path::PathBuf;
+
+	#[test]
+	fn sources() {
+		let loader = Loader::new(7)
+			.crate_dir("/ws/day_7")
+			.crate_dir("/ws/day_7")
+			.crate_dir("/elsewhere/day_7");
+		assert_eq!(
+			loader.sources(),
+			vec![
+				Source::File(PathBuf::from("/ws/day_7/input.txt")),
+				Source::File(PathBuf::from("/elsewhere/day_7/input.txt")),
+				Source::File(PathBuf::from("day_7/input.txt")),
+			]
+		);
+
+		let loader = loader.env(Some("from_env.txt".to_string()));
+		assert_eq!(
+			loader.sources(),
+			vec![Source::File(PathBuf::from("from_env.txt"))]
+		);
+		assert_eq!(
+			loader.arg(Some("-".to_string())).sources(),
+			vec![Source::Stdin]
+		);
+	}
+
+	#[test]
+	fn load() {
+		let dir = tempfile::tempdir().unwrap();
+		let missing = dir.path().join("missing");
+		fs::write(dir.path().join("input.txt"), "1\n").unwrap();
+
+		let loader = Loader::new(25).crate_dir(&missing).crate_dir(dir.path());
+		assert_eq!(loader.load().unwrap(), "1\n");
+
+		let err = Loader::new(25)
+			.crate_dir(&missing)
+			.load()
+			.unwrap_err()
+			.to_string();
+		assert!(err.starts_with("failed to read the input of day 25, tried:\n"));
+		assert!(err.contains(&format!("\n  {}: ", missing.join("input.txt").display())));
+		assert!(err.contains("\n  day_25/input.txt: "));
+	}
+}
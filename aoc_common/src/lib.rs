@@ -0,0 +1,145 @@
+//! What the day crates share: the [Solution] trait every day implements, and running a solution,
+//! on its own from the `main` of its crate or along with the other days from the [registry].
+
+pub mod grid;
+pub mod input;
+pub mod registry;
+
+use anyhow::{bail, Context};
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+/// The solution of a day. The input is parsed once, before the parts, and timed on its own.
+pub trait Solution {
+	const DAY: u32;
+
+	type Input;
+
+	fn parse(input: &str) -> anyhow::Result<Self::Input>;
+
+	fn part_1(input: &Self::Input) -> anyhow::Result<impl Display>;
+
+	fn part_2(input: &Self::Input) -> anyhow::Result<impl Display>;
+}
+
+/// The answers of both parts, with how long each step took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+	pub part_1: String,
+	pub part_2: String,
+	pub parse_time: Duration,
+	pub part_1_time: Duration,
+	pub part_2_time: Duration,
+}
+
+pub fn solve<S: Solution>(input: &str) -> anyhow::Result<Answers> {
+	let start = Instant::now();
+	let input = S::parse(input).context("failed to parse the input")?;
+	let parse_time = start.elapsed();
+
+	let start = Instant::now();
+	let part_1 = S::part_1(&input).context("part 1 failed")?.to_string();
+	let part_1_time = start.elapsed();
+
+	let start = Instant::now();
+	let part_2 = S::part_2(&input).context("part 2 failed")?.to_string();
+	let part_2_time = start.elapsed();
+
+	Ok(Answers {
+		part_1,
+		part_2,
+		parse_time,
+		part_1_time,
+		part_2_time,
+	})
+}
+
+/// Makes [main] run only part 1 or 2, `aqa_aoc run --part P` sets it.
+pub const PART_ENV: &str = "AOC_PART";
+
+/// Makes [main] print `Time N: <nanoseconds>ns` before each part, for `aqa_aoc run` to show.
+pub const TIMINGS_ENV: &str = "AOC_TIMINGS";
+
+/// `main` of a day crate, `manifest_dir` is its `env!("CARGO_MANIFEST_DIR")`, see
+/// [input::Loader::from_env] for where the input is read from. Prints the `Part 1: ...` and
+/// `Part 2: ...` lines that `aqa_aoc run` looks for, each as soon as it's known.
+pub fn main<S: Solution>(manifest_dir: &str) -> anyhow::Result<()> {
+	let only_part = match std::env::var(PART_ENV) {
+		Ok(part) if part == "1" || part == "2" => part.parse().ok(),
+		Ok(part) => bail!("{} must be 1 or 2, got `{}`", PART_ENV, part),
+		Err(_) => None,
+	};
+	let timings = std::env::var_os(TIMINGS_ENV).is_some();
+
+	let input = S::parse(&input::Loader::from_env(S::DAY, manifest_dir).load()?)?;
+	if only_part != Some(2) {
+		let start = Instant::now();
+		let answer = S::part_1(&input)?.to_string();
+		print_part(1, &answer, timings.then(|| start.elapsed()));
+	}
+	if only_part != Some(1) {
+		let start = Instant::now();
+		let answer = S::part_2(&input)?.to_string();
+		print_part(2, &answer, timings.then(|| start.elapsed()));
+	}
+	Ok(())
+}
+
+fn print_part(part: u32, answer: &str, elapsed: Option<Duration>) {
+	if let Some(elapsed) = elapsed {
+		println!("Time {}: {}ns", part, elapsed.as_nanos());
+	}
+	println!("{}", part_line(part, &answer));
+}
+
+/// Answers that span lines, like letters drawn on a screen, start on a line of their own.
+fn part_line(part: u32, answer: &dyn Display) -> String {
+	let answer = answer.to_string();
+	if answer.contains('\n') {
+		format!("Part {}:\n{}", part, answer)
+	} else {
+		format!("Part {}: {}", part, answer)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Solution;
+	use std::fmt::Display;
+
+	pub struct Sum;
+
+	impl Solution for Sum {
+		const DAY: u32 = 1;
+
+		type Input = Vec<u64>;
+
+		fn parse(input: &str) -> anyhow::Result<Vec<u64>> {
+			Ok(input.lines().map(str::parse).collect::<Result<_, _>>()?)
+		}
+
+		fn part_1(input: &Vec<u64>) -> anyhow::Result<impl Display> {
+			Ok(input.iter().sum::<u64>())
+		}
+
+		fn part_2(input: &Vec<u64>) -> anyhow::Result<impl Display> {
+			Ok(format!("max {}", input.iter().max().unwrap_or(&0)))
+		}
+	}
+
+	#[test]
+	fn solve() {
+		let answers = super::solve::<Sum>("1\n5\n3\n").unwrap();
+		assert_eq!(answers.part_1, "9");
+		assert_eq!(answers.part_2, "max 5");
+
+		let err = super::solve::<Sum>("1\nx\n").unwrap_err();
+		assert_eq!(err.to_string(), "failed to parse the input");
+	}
+
+	#[test]
+	fn part_line() {
+		assert_eq!(super::part_line(1, &24000), "Part 1: 24000");
+		assert_eq!(super::part_line(2, &"#..\n.#."), "Part 2:\n#..\n.#.");
+	}
+}
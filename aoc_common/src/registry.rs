@@ -0,0 +1,153 @@
+//! The days of the workspace, for the `aoc` binary that runs any number of them.
+
+use crate::input::Loader;
+use crate::{Answers, Solution};
+use anyhow::{anyhow, bail};
+use std::path::Path;
+
+pub struct Entry {
+	pub day: u32,
+	solve: fn(&str) -> anyhow::Result<Answers>,
+}
+
+impl Entry {
+	pub fn of<S: Solution>() -> Entry {
+		Entry {
+			day: S::DAY,
+			solve: crate::solve::<S>,
+		}
+	}
+
+	pub fn solve(&self, input: &str) -> anyhow::Result<Answers> {
+		(self.solve)(input)
+	}
+}
+
+/// Entries of the given day crates, each of which exports its [Solution] as `Day`.
+#[macro_export]
+macro_rules! registry {
+	($($day_crate:ident),* $(,)?) => {
+		vec![$($crate::registry::Entry::of::<$day_crate::Day>()),*]
+	};
+}
+
+/// `main` of the `aoc` binary: `aoc [DAY...]` runs the given days, or every day in `entries`.
+/// Keeps going when a day fails, and fails at the end. Inputs are read from the `day_N` crates in
+/// `workspace_root`, or in the working directory.
+pub fn main(workspace_root: &Path, entries: &[Entry]) -> anyhow::Result<()> {
+	let days = std::env::args()
+		.skip(1)
+		.map(|day| {
+			day.parse::<u32>()
+				.map_err(|_| anyhow!("expected day numbers, got `{}`\nUsage: aoc [DAY...]", day))
+		})
+		.collect::<anyhow::Result<Vec<_>>>()?;
+
+	let mut failed = Vec::new();
+	for entry in select(entries, &days)? {
+		println!("Day {}", entry.day);
+		let loader =
+			Loader::new(entry.day).crate_dir(workspace_root.join(format!("day_{}", entry.day)));
+		match loader.load().and_then(|input| entry.solve(&input)) {
+			Ok(answers) => {
+				println!("  Parsing  ({:.2?})", answers.parse_time);
+				print_part(1, &answers.part_1, answers.part_1_time);
+				print_part(2, &answers.part_2, answers.part_2_time);
+			}
+			Err(err) => {
+				println!("  Error: {:#}", err);
+				failed.push(entry.day);
+			}
+		}
+	}
+
+	match failed.as_slice() {
+		[] => Ok(()),
+		[day] => bail!("day {} failed", day),
+		days => bail!("days {:?} failed", days),
+	}
+}
+
+fn print_part(part: u32, answer: &str, time: std::time::Duration) {
+	if answer.contains('\n') {
+		println!("  Part {}: ({:.2?})\n{}", part, time, answer);
+	} else {
+		println!("  Part {}: {}  ({:.2?})", part, answer, time);
+	}
+}
+
+/// The entries of `days` in day order, all of them when no day is given.
+fn select<'a>(entries: &'a [Entry], days: &[u32]) -> anyhow::Result<Vec<&'a Entry>> {
+	let mut selected = if days.is_empty() {
+		entries.iter().collect::<Vec<_>>()
+	} else {
+		days.iter()
+			.map(|day| {
+				entries
+					.iter()
+					.find(|entry| entry.day == *day)
+					.ok_or_else(|| anyhow!("day {} is not registered", day))
+			})
+			.collect::<anyhow::Result<Vec<_>>>()?
+	};
+	selected.sort_by_key(|entry| entry.day);
+	selected.dedup_by_key(|entry| entry.day);
+	Ok(selected)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Entry;
+	use crate::Solution;
+	use std::fmt::Display;
+
+	macro_rules! day {
+		($name:ident, $day:literal) => {
+			struct $name;
+
+			impl Solution for $name {
+				const DAY: u32 = $day;
+
+				type Input = String;
+
+				fn parse(input: &str) -> anyhow::Result<String> {
+					Ok(input.trim().to_string())
+				}
+
+				fn part_1(input: &String) -> anyhow::Result<impl Display> {
+					Ok(input.len())
+				}
+
+				fn part_2(input: &String) -> anyhow::Result<impl Display> {
+					Ok(input.to_uppercase())
+				}
+			}
+		};
+	}
+
+	day!(Day3, 3);
+	day!(Day7, 7);
+
+	#[test]
+	fn select() {
+		let entries = [Entry::of::<Day7>(), Entry::of::<Day3>()];
+		let days =
+			|selected: Vec<&Entry>| selected.iter().map(|entry| entry.day).collect::<Vec<_>>();
+
+		assert_eq!(days(super::select(&entries, &[]).unwrap()), vec![3, 7]);
+		assert_eq!(
+			days(super::select(&entries, &[7, 3, 7]).unwrap()),
+			vec![3, 7]
+		);
+		assert_eq!(
+			super::select(&entries, &[4]).err().unwrap().to_string(),
+			"day 4 is not registered"
+		);
+
+		let answers = entries[1].solve("abc\n").unwrap();
+		assert_eq!(
+			(answers.part_1.as_str(), answers.part_2.as_str()),
+			("3", "ABC")
+		);
+	}
+}
@@ -0,0 +1,252 @@
+//! The answers.toml ledger of a day crate: every submitted answer with its outcome, and the bounds
+//! learned from "too high"/"too low" replies. Submissions that are sure to be wrong are refused, a
+//! wrong answer locks out further ones for a while.
+
+use crate::submit::Outcome;
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ledger {
+	#[serde(default)]
+	pub part_1: PartLedger,
+	#[serde(default)]
+	pub part_2: PartLedger,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartLedger {
+	/// The accepted answer
+	pub answer: Option<String>,
+	/// Highest answer known to be too low
+	pub too_low: Option<i64>,
+	/// Lowest answer known to be too high
+	pub too_high: Option<i64>,
+	#[serde(default)]
+	pub attempts: Vec<Attempt>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+	pub answer: String,
+	pub outcome: Verdict,
+	/// Unix time of the submission
+	pub at: u64,
+}
+
+/// The outcomes worth remembering, the others say nothing about the answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+	Correct,
+	Wrong,
+	TooHigh,
+	TooLow,
+}
+
+/// Why an answer isn't worth submitting.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+	AlreadyCorrect,
+	AlreadySolved { answer: String },
+	KnownWrong { verdict: Verdict, at: u64 },
+	TooHigh { bound: i64 },
+	TooLow { bound: i64 },
+}
+
+impl Display for Refusal {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Refusal::AlreadyCorrect => write!(f, "this answer was already accepted"),
+			Refusal::AlreadySolved { answer } => {
+				write!(f, "this part was already solved with {}", answer)
+			}
+			Refusal::KnownWrong { verdict, at } => write!(
+				f,
+				"this answer was submitted on {} and was {}",
+				crate::date::format_unix(*at),
+				match verdict {
+					Verdict::TooHigh => "too high",
+					Verdict::TooLow => "too low",
+					_ => "wrong",
+				}
+			),
+			Refusal::TooHigh { bound } => write!(f, "{} was already too high", bound),
+			Refusal::TooLow { bound } => write!(f, "{} was already too low", bound),
+		}
+	}
+}
+
+impl Verdict {
+	pub fn from_outcome(outcome: Outcome) -> Option<Verdict> {
+		match outcome {
+			Outcome::Correct => Some(Verdict::Correct),
+			Outcome::Wrong => Some(Verdict::Wrong),
+			Outcome::TooHigh => Some(Verdict::TooHigh),
+			Outcome::TooLow => Some(Verdict::TooLow),
+			Outcome::RateLimited { .. } | Outcome::AlreadySolved => None,
+		}
+	}
+}
+
+pub fn ledger_path(crate_dir: &Path) -> PathBuf {
+	crate_dir.join(ANSWERS_FILE)
+}
+
+impl Ledger {
+	/// An empty ledger when the day has none yet.
+	pub fn load(crate_dir: &Path) -> anyhow::Result<Ledger> {
+		let path = ledger_path(crate_dir);
+		match fs::read(&path) {
+			Ok(ledger) => toml::from_slice(&ledger)
+				.with_context(|| format!("failed to parse {}", path.display())),
+			Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Ledger::default()),
+			Err(err) => Err(err).with_context(|| format!("failed to read {}", path.display())),
+		}
+	}
+
+	pub fn save(&self, crate_dir: &Path) -> anyhow::Result<()> {
+		let path = ledger_path(crate_dir);
+		fs::write(&path, toml::to_string(self)?)
+			.with_context(|| format!("failed to write {}", path.display()))
+	}
+
+	pub fn part(&self, part: u32) -> &PartLedger {
+		match part {
+			1 => &self.part_1,
+			_ => &self.part_2,
+		}
+	}
+
+	pub fn part_mut(&mut self, part: u32) -> &mut PartLedger {
+		match part {
+			1 => &mut self.part_1,
+			_ => &mut self.part_2,
+		}
+	}
+}
+
+impl PartLedger {
+	/// `Err` when the ledger already tells how AoC would answer.
+	pub fn check(&self, answer: &str) -> Result<(), Refusal> {
+		let answer = answer.trim();
+
+		if let Some(correct) = &self.answer {
+			return Err(if correct == answer {
+				Refusal::AlreadyCorrect
+			} else {
+				Refusal::AlreadySolved {
+					answer: correct.clone(),
+				}
+			});
+		}
+
+		if let Some(attempt) = self
+			.attempts
+			.iter()
+			.find(|attempt| attempt.answer == answer)
+		{
+			return Err(Refusal::KnownWrong {
+				verdict: attempt.outcome,
+				at: attempt.at,
+			});
+		}
+
+		// Answers that aren't numbers can't be compared to the bounds
+		if let Ok(number) = answer.parse::<i64>() {
+			match (self.too_low, self.too_high) {
+				(Some(bound), _) if number <= bound => return Err(Refusal::TooLow { bound }),
+				(_, Some(bound)) if number >= bound => return Err(Refusal::TooHigh { bound }),
+				_ => (),
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Adds the attempt, narrowing the bounds or setting the answer.
+	pub fn record(&mut self, answer: &str, verdict: Verdict, at: u64) {
+		let answer = answer.trim().to_string();
+		let number = answer.parse::<i64>().ok();
+
+		match verdict {
+			Verdict::Correct => self.answer = Some(answer.clone()),
+			Verdict::TooLow => {
+				self.too_low = self.too_low.max(number);
+			}
+			Verdict::TooHigh => {
+				self.too_high = match (self.too_high, number) {
+					(Some(bound), Some(number)) => Some(bound.min(number)),
+					(bound, number) => bound.or(number),
+				};
+			}
+			Verdict::Wrong => (),
+		}
+
+		self.attempts.push(Attempt {
+			answer,
+			outcome: verdict,
+			at,
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Ledger, Refusal, Verdict};
+
+	#[test]
+	fn refuses_what_is_known() {
+		let mut ledger = Ledger::default();
+		let part = ledger.part_mut(1);
+		assert_eq!(part.check("42"), Ok(()));
+
+		part.record("100", Verdict::TooHigh, 1);
+		part.record("200", Verdict::TooHigh, 2);
+		part.record(" 10\n", Verdict::TooLow, 3);
+		part.record("abc", Verdict::Wrong, 4);
+		assert_eq!((part.too_low, part.too_high), (Some(10), Some(100)));
+
+		assert_eq!(part.check("150"), Err(Refusal::TooHigh { bound: 100 }));
+		assert_eq!(part.check("5"), Err(Refusal::TooLow { bound: 10 }));
+		assert_eq!(
+			part.check("abc"),
+			Err(Refusal::KnownWrong {
+				verdict: Verdict::Wrong,
+				at: 4
+			})
+		);
+		assert_eq!(part.check("42"), Ok(()));
+
+		part.record("42", Verdict::Correct, 5);
+		assert_eq!(part.check("42"), Err(Refusal::AlreadyCorrect));
+		assert_eq!(
+			part.check("43"),
+			Err(Refusal::AlreadySolved {
+				answer: "42".to_string()
+			})
+		);
+		assert_eq!(ledger.part(2).check("42"), Ok(()));
+	}
+
+	#[test]
+	fn save_and_load() {
+		let dir = tempfile::tempdir().unwrap();
+		assert_eq!(Ledger::load(dir.path()).unwrap(), Ledger::default());
+
+		let mut ledger = Ledger::default();
+		ledger.part_mut(1).record("7", Verdict::TooLow, 1669870900);
+		ledger.part_mut(1).record("9", Verdict::Correct, 1669871000);
+		ledger.save(dir.path()).unwrap();
+
+		let saved = std::fs::read_to_string(super::ledger_path(dir.path())).unwrap();
+		assert!(saved.contains("answer = \"9\"\ntoo_low = 7\n"));
+		assert!(saved.contains("outcome = \"too_low\""));
+		assert_eq!(Ledger::load(dir.path()).unwrap(), ledger);
+	}
+}
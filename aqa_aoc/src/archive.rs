@@ -0,0 +1,383 @@
+//! Bundles of a finished year: a directory with copies of the day crates and an archive.toml
+//! manifest with their answers, input hashes and timings. A bundle restores the crates into a
+//! workspace.
+
+use crate::answers::Ledger;
+use crate::cache::{self, Storage};
+use crate::date;
+use crate::gitignore;
+use crate::layout::{DayCrate, Layout};
+use crate::run::{self, PartResult};
+use crate::workspace::Workspace;
+use anyhow::{bail, Context};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const MANIFEST_FILE: &str = "archive.toml";
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+	pub year: u32,
+	pub layout: Layout,
+	/// Unix time of the archiving
+	pub archived_at: u64,
+	#[serde(default, rename = "day")]
+	pub days: Vec<ArchivedDay>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedDay {
+	pub day: u32,
+	/// Directory of the crate, in the bundle and in the workspace
+	pub member: String,
+	pub input_sha256: Option<String>,
+	#[serde(default)]
+	pub part_1: ArchivedPart,
+	#[serde(default)]
+	pub part_2: ArchivedPart,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedPart {
+	/// The accepted answer, from answers.toml
+	pub answer: Option<String>,
+	/// What the solution printed when it was archived
+	pub printed: Option<String>,
+	/// Time of the part in microseconds, as the solution reported it, see [PartResult::elapsed]
+	pub time_us: Option<u64>,
+}
+
+impl ArchivedDay {
+	/// The day with its answers and input hash, the timings are added by [ArchivedDay::add_run].
+	pub fn new(root: &Path, day_crate: &DayCrate) -> anyhow::Result<ArchivedDay> {
+		let dir = root.join(day_crate.dir());
+		let ledger = Ledger::load(&dir)?;
+
+		let input_path = cache::input_path(&dir);
+		let input_sha256 = match fs::read(&input_path) {
+			Ok(input) => Some(cache::sha256_hex(input)),
+			Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+			Err(err) => {
+				return Err(err).with_context(|| format!("failed to read {}", input_path.display()))
+			}
+		};
+
+		Ok(ArchivedDay {
+			day: day_crate.day,
+			member: day_crate.member(),
+			input_sha256,
+			part_1: ArchivedPart {
+				answer: ledger.part_1.answer,
+				..ArchivedPart::default()
+			},
+			part_2: ArchivedPart {
+				answer: ledger.part_2.answer,
+				..ArchivedPart::default()
+			},
+		})
+	}
+
+	pub fn add_run(&mut self, result: PartResult) {
+		let part = match result.part {
+			1 => &mut self.part_1,
+			_ => &mut self.part_2,
+		};
+		part.printed = Some(result.answer);
+		part.time_us = result.elapsed.map(|elapsed| elapsed.as_micros() as u64);
+	}
+}
+
+impl Manifest {
+	pub fn load(bundle: &Path) -> anyhow::Result<Manifest> {
+		let path = bundle.join(MANIFEST_FILE);
+		toml::from_slice(
+			&fs::read(&path).with_context(|| format!("failed to read {}", path.display()))?,
+		)
+		.with_context(|| format!("failed to parse {}", path.display()))
+	}
+
+	pub fn save(&self, bundle: &Path) -> anyhow::Result<()> {
+		let path = bundle.join(MANIFEST_FILE);
+		fs::write(&path, toml::to_string(self)?)
+			.with_context(|| format!("failed to write {}", path.display()))
+	}
+}
+
+/// Where [archive] puts the bundle of `year` when it isn't told.
+pub fn default_bundle(year: u32) -> PathBuf {
+	PathBuf::from(format!("aoc_{}", year))
+}
+
+/// What [archive] did.
+#[derive(Debug)]
+pub struct Archived {
+	pub days: Vec<DayCrate>,
+	/// Solutions that failed to run, archived without timings
+	pub failed_runs: Vec<(DayCrate, anyhow::Error)>,
+}
+
+/// Copies the day crates of the workspace's year to `bundle`, running every solution in release
+/// mode for the timings. `on_run` is called before each run. `remove` takes the crates out of the
+/// workspace, and the runner, afterwards, with their inputs in `inputs`.
+pub fn archive(
+	workspace: &mut Workspace,
+	inputs: Storage,
+	bundle: &Path,
+	remove: bool,
+	on_run: impl FnMut(&DayCrate),
+) -> anyhow::Result<Archived> {
+	if bundle.exists() {
+		bail!("{} already exists", bundle.display());
+	}
+
+	let days = workspace
+		.days()?
+		.into_iter()
+		.filter(|day| day.is_member && day.has_crate)
+		.map(|day| day.day_crate)
+		.collect::<Vec<_>>();
+	if days.is_empty() {
+		bail!("no day crates of {} to archive", workspace.year);
+	}
+
+	// Written next to the bundle and moved into place once complete, a failure halfway through
+	// doesn't leave a bundle that blocks the next try
+	let partial = partial_bundle(bundle);
+	if partial.exists() {
+		fs::remove_dir_all(&partial)
+			.with_context(|| format!("failed to remove {}", partial.display()))?;
+	}
+	let failed_runs = match write_bundle(workspace, &days, &partial, on_run) {
+		Ok(failed_runs) => failed_runs,
+		Err(err) => {
+			let _ = fs::remove_dir_all(&partial);
+			return Err(err);
+		}
+	};
+	fs::rename(&partial, bundle).with_context(|| {
+		format!(
+			"failed to move {} to {}",
+			partial.display(),
+			bundle.display()
+		)
+	})?;
+
+	if remove {
+		for day_crate in &days {
+			workspace.remove_member(&day_crate.member())?;
+			workspace.unregister_day(day_crate)?;
+		}
+		workspace.save()?;
+
+		for day_crate in &days {
+			let dir = workspace.root().join(day_crate.dir());
+			if let Some(stored_at) = inputs.outside_crate(workspace.root(), &day_crate.dir()) {
+				if stored_at.exists() {
+					fs::remove_file(&stored_at)
+						.with_context(|| format!("failed to remove {}", stored_at.display()))?;
+				}
+			}
+			fs::remove_dir_all(&dir)
+				.with_context(|| format!("failed to remove {}", dir.display()))?;
+		}
+	}
+
+	Ok(Archived { days, failed_runs })
+}
+
+/// `.<name>.partial`, in the directory of `bundle`.
+fn partial_bundle(bundle: &Path) -> PathBuf {
+	let name = bundle
+		.file_name()
+		.map(|name| name.to_string_lossy().into_owned())
+		.unwrap_or_default();
+	bundle.with_file_name(format!(".{}.partial", name))
+}
+
+/// Runs and copies every day crate into `dir`, with the manifest. Returns the runs that failed.
+fn write_bundle(
+	workspace: &Workspace,
+	days: &[DayCrate],
+	dir: &Path,
+	mut on_run: impl FnMut(&DayCrate),
+) -> anyhow::Result<Vec<(DayCrate, anyhow::Error)>> {
+	let mut manifest = Manifest {
+		year: workspace.year,
+		layout: workspace.layout,
+		archived_at: date::now_unix(),
+		days: Vec::new(),
+	};
+	let mut failed_runs = Vec::new();
+	for day_crate in days {
+		let mut day = ArchivedDay::new(workspace.root(), day_crate)?;
+		on_run(day_crate);
+		if let Err(err) = run::run(day_crate, None, true, |result| day.add_run(result)) {
+			failed_runs.push((*day_crate, err));
+		}
+		copy_crate(
+			&workspace.root().join(day_crate.dir()),
+			&dir.join(day_crate.dir()),
+		)?;
+		manifest.days.push(day);
+	}
+	manifest.save(dir)?;
+
+	Ok(failed_runs)
+}
+
+/// What [restore] did, by member.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Restored {
+	pub year: u32,
+	pub restored: Vec<String>,
+	/// Crates that exist already, they are left alone
+	pub existing: Vec<String>,
+	/// Restored crates whose input doesn't match the hash in the manifest
+	pub mismatched_inputs: Vec<String>,
+}
+
+/// Copies the day crates of a bundle made by [archive] back into the workspace in `root`, and
+/// makes them members and days of the runner, see [Workspace::register_day]. Inputs go to
+/// `inputs`.
+pub fn restore(root: &Path, inputs: Storage, bundle: &Path) -> anyhow::Result<Restored> {
+	let manifest = Manifest::load(bundle)?;
+	let mut workspace = Workspace::open(root, manifest.layout, manifest.year)?;
+
+	let mut restored = Restored {
+		year: manifest.year,
+		restored: Vec::new(),
+		existing: Vec::new(),
+		mismatched_inputs: Vec::new(),
+	};
+	for day in &manifest.days {
+		let crate_dir = root.join(&day.member);
+		if crate_dir.exists() {
+			restored.existing.push(day.member.clone());
+			continue;
+		}
+
+		copy_crate(&bundle.join(&day.member), &crate_dir)?;
+		if let Some(stored_at) = inputs.outside_crate(root, Path::new(&day.member)) {
+			cache::move_input(&crate_dir, &stored_at)?;
+		}
+		workspace.add_member(&day.member)?;
+		workspace.register_day(&workspace.day_crate(day.day))?;
+
+		let input_sha256 = fs::read(cache::input_path(&crate_dir))
+			.ok()
+			.map(cache::sha256_hex);
+		if input_sha256 != day.input_sha256 {
+			restored.mismatched_inputs.push(day.member.clone());
+		}
+		restored.restored.push(day.member.clone());
+	}
+	workspace.save()?;
+
+	if !restored.restored.is_empty() && inputs == Storage::Cache {
+		gitignore::update(root, &gitignore::input_entries(manifest.layout))?;
+	}
+
+	Ok(restored)
+}
+
+/// Copies the crate in `from` to `to`, which must not exist, leaving out build output. Links are
+/// copied as the files they point to, an input in the input cache ends up in the copy.
+pub fn copy_crate(from: &Path, to: &Path) -> anyhow::Result<()> {
+	if to.exists() {
+		bail!("{} already exists", to.display());
+	}
+	copy_dir(from, to)
+}
+
+fn copy_dir(from: &Path, to: &Path) -> anyhow::Result<()> {
+	fs::create_dir_all(to).with_context(|| format!("failed to create {}", to.display()))?;
+
+	for entry in fs::read_dir(from).with_context(|| format!("failed to read {}", from.display()))? {
+		let entry = entry?;
+		if entry.file_name() == "target" {
+			continue;
+		}
+
+		let path = entry.path();
+		let target = to.join(entry.file_name());
+		if path.is_dir() {
+			copy_dir(&path, &target)?;
+		} else {
+			fs::copy(&path, &target).with_context(|| {
+				format!("failed to copy {} to {}", path.display(), target.display())
+			})?;
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{ArchivedDay, ArchivedPart, Manifest};
+	use crate::layout::Layout;
+	use crate::run::PartResult;
+	use std::fs;
+	use std::time::Duration;
+
+	#[test]
+	fn archived_day() {
+		let root = tempfile::tempdir().unwrap();
+		let day_crate = Layout::Flat.day_crate(2022, 1);
+		fs::create_dir_all(root.path().join("day_1/target/debug")).unwrap();
+		fs::write(root.path().join("day_1/input.txt"), "abc").unwrap();
+		fs::write(
+			root.path().join("day_1/answers.toml"),
+			"[part_1]\nanswer = \"24000\"\n",
+		)
+		.unwrap();
+
+		let mut day = ArchivedDay::new(root.path(), &day_crate).unwrap();
+		day.add_run(PartResult {
+			part: 2,
+			answer: "45000".to_string(),
+			elapsed: Some(Duration::from_micros(1500)),
+		});
+		assert_eq!(
+			day,
+			ArchivedDay {
+				day: 1,
+				member: "day_1".to_string(),
+				input_sha256: Some(
+					"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".to_string()
+				),
+				part_1: ArchivedPart {
+					answer: Some("24000".to_string()),
+					printed: None,
+					time_us: None,
+				},
+				part_2: ArchivedPart {
+					answer: None,
+					printed: Some("45000".to_string()),
+					time_us: Some(1500),
+				},
+			}
+		);
+
+		let bundle = tempfile::tempdir().unwrap();
+		super::copy_crate(&root.path().join("day_1"), &bundle.path().join("day_1")).unwrap();
+		assert_eq!(
+			fs::read_to_string(bundle.path().join("day_1/input.txt")).unwrap(),
+			"abc"
+		);
+		assert!(!bundle.path().join("day_1/target").exists());
+		assert!(
+			super::copy_crate(&root.path().join("day_1"), &bundle.path().join("day_1")).is_err()
+		);
+
+		let manifest = Manifest {
+			year: 2022,
+			layout: Layout::Flat,
+			archived_at: 1672531200,
+			days: vec![day],
+		};
+		manifest.save(bundle.path()).unwrap();
+		assert_eq!(Manifest::load(bundle.path()).unwrap(), manifest);
+	}
+}
@@ -0,0 +1,397 @@
+//! Puzzle inputs on disk, with a metadata sidecar so that each input is downloaded only once.
+
+use crate::config::{self, AocCfg, STATE_DIR};
+use crate::gitignore;
+use crate::layout;
+use crate::workspace::Workspace;
+use anyhow::{bail, Context};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+pub const INPUT_FILE: &str = "input.txt";
+pub const META_FILE: &str = "input.meta.toml";
+
+/// Where the inputs are kept, `inputs` in aoc_cfg.toml.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Storage {
+	/// `input.txt` of the day crate, committed with the crate
+	#[default]
+	InTree,
+	/// Under `.aqa_aoc/inputs`, which isn't committed. `input.txt` of the day crate links to it,
+	/// so the solutions read it from the same path.
+	Cache,
+}
+
+impl Storage {
+	/// Where the input of the crate in `crate_dir`, relative to the workspace `root`, is stored
+	/// when it isn't stored in the crate.
+	pub fn outside_crate(self, root: &Path, crate_dir: &Path) -> Option<PathBuf> {
+		match self {
+			Storage::InTree => None,
+			Storage::Cache => Some(
+				root.join(STATE_DIR)
+					.join("inputs")
+					.join(crate_dir)
+					.join(INPUT_FILE),
+			),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputMeta {
+	pub year: u32,
+	pub day: u32,
+	/// Unix time of the download
+	pub fetched_at: u64,
+	pub sha256: String,
+	/// Fingerprint of the session cookie the input was downloaded with. Inputs differ per account.
+	pub session: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Cached {
+	Missing,
+	/// The input exists, but wasn't downloaded by this tool
+	Untracked,
+	Fetched(InputMeta),
+	/// The input doesn't match the hash recorded when it was downloaded
+	Modified(InputMeta),
+}
+
+pub fn input_path(crate_dir: &Path) -> PathBuf {
+	crate_dir.join(INPUT_FILE)
+}
+
+pub fn lookup(crate_dir: &Path) -> anyhow::Result<Cached> {
+	let input = match fs::read(input_path(crate_dir)) {
+		Ok(input) => input,
+		Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Cached::Missing),
+		Err(err) => return Err(err.into()),
+	};
+
+	let meta_path = crate_dir.join(META_FILE);
+	let meta = match fs::read(&meta_path) {
+		Ok(meta) => toml::from_slice::<InputMeta>(&meta)
+			.with_context(|| format!("failed to parse {}", meta_path.display()))?,
+		Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Cached::Untracked),
+		Err(err) => return Err(err.into()),
+	};
+
+	if meta.sha256 == sha256_hex(&input) {
+		Ok(Cached::Fetched(meta))
+	} else {
+		Ok(Cached::Modified(meta))
+	}
+}
+
+/// Writes the input and its sidecar. The input goes to `stored_at` when it's stored outside the
+/// crate, see [Storage::outside_crate], with a link to it from `input.txt` of the crate.
+pub fn store(
+	crate_dir: &Path,
+	stored_at: Option<&Path>,
+	meta: &InputMeta,
+	input: &str,
+) -> anyhow::Result<()> {
+	let input_path = input_path(crate_dir);
+	// Replaced instead of written through, it may be a link to where the input was stored before
+	remove_input(&input_path)?;
+
+	match stored_at {
+		Some(stored_at) => {
+			if let Some(parent) = stored_at.parent() {
+				fs::create_dir_all(parent)
+					.with_context(|| format!("failed to create {}", parent.display()))?;
+			}
+			fs::write(stored_at, input)
+				.with_context(|| format!("failed to write {}", stored_at.display()))?;
+			link(&input_path, stored_at)?;
+		}
+		None => fs::write(&input_path, input)
+			.with_context(|| format!("failed to write {}", input_path.display()))?,
+	}
+
+	let meta_path = crate_dir.join(META_FILE);
+	fs::write(&meta_path, toml::to_string(meta)?)
+		.with_context(|| format!("failed to write {}", meta_path.display()))
+}
+
+/// Moves the input of the crate in `crate_dir` to `stored_at` and links to it. `false` when there's
+/// no input, or it already is a link.
+pub fn move_input(crate_dir: &Path, stored_at: &Path) -> anyhow::Result<bool> {
+	let input_path = input_path(crate_dir);
+	match fs::symlink_metadata(&input_path) {
+		Ok(meta) if meta.is_file() => (),
+		Ok(_) => return Ok(false),
+		Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+		Err(err) => {
+			return Err(err).with_context(|| format!("failed to read {}", input_path.display()))
+		}
+	}
+
+	if let Some(parent) = stored_at.parent() {
+		fs::create_dir_all(parent)
+			.with_context(|| format!("failed to create {}", parent.display()))?;
+	}
+	fs::rename(&input_path, stored_at).with_context(|| {
+		format!(
+			"failed to move {} to {}",
+			input_path.display(),
+			stored_at.display()
+		)
+	})?;
+	link(&input_path, stored_at)?;
+	Ok(true)
+}
+
+/// What [migrate] did.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Migration {
+	/// Inputs, relative to the workspace root, with where they were moved to
+	pub moved: Vec<(PathBuf, PathBuf)>,
+	pub gitignore_updated: bool,
+	/// The moved inputs and their metadata were removed from the git index
+	pub untracked: bool,
+	/// aoc_cfg.toml was switched to [Storage::Cache]
+	pub cfg_updated: bool,
+}
+
+/// Moves the inputs of every day crate out of the tracked tree, see [Storage::Cache], removes them
+/// and their metadata from the git index and switches aoc_cfg.toml to keep new ones there too.
+pub fn migrate(workspace: &Workspace, cfg: &AocCfg) -> anyhow::Result<Migration> {
+	let root = workspace.root();
+
+	let mut migration = Migration::default();
+	for member in workspace.members()? {
+		if layout::day_key(member).is_none() {
+			continue;
+		}
+		// SAFETY: inputs in the cache are always outside the crate
+		let stored_at = Storage::Cache
+			.outside_crate(root, Path::new(member))
+			.unwrap();
+		if move_input(&root.join(member), &stored_at)? {
+			migration
+				.moved
+				.push((input_path(Path::new(member)), stored_at));
+		}
+	}
+
+	migration.gitignore_updated =
+		gitignore::update(root, &gitignore::input_entries(workspace.layout))?;
+
+	if !migration.moved.is_empty() {
+		let untracked = migration
+			.moved
+			.iter()
+			.flat_map(|(input, _)| [input.clone(), input.with_file_name(META_FILE)])
+			.collect::<Vec<_>>();
+		migration.untracked = untrack(root, &untracked)?;
+	}
+
+	if cfg.inputs != Storage::Cache {
+		config::set_value(root, "inputs", "cache")?;
+		migration.cfg_updated = true;
+	}
+
+	Ok(migration)
+}
+
+/// Removes `paths`, relative to `root`, from the git index, leaving the files alone. `false`
+/// outside of a git repository.
+fn untrack(root: &Path, paths: &[PathBuf]) -> anyhow::Result<bool> {
+	let in_repository = Command::new("git")
+		.args(["rev-parse", "--is-inside-work-tree"])
+		.current_dir(root)
+		.output()
+		.is_ok_and(|output| output.status.success());
+	if !in_repository {
+		return Ok(false);
+	}
+
+	// --force because `input.txt` is a link now, which git takes for a change. The contents of the
+	// inputs are in the cache, only the index is touched.
+	let status = Command::new("git")
+		.args([
+			"rm",
+			"--cached",
+			"--force",
+			"--quiet",
+			"--ignore-unmatch",
+			"--",
+		])
+		.args(paths)
+		.current_dir(root)
+		.status()
+		.context("failed to run git rm")?;
+	if !status.success() {
+		bail!(
+			"git rm --cached failed with code {}",
+			status.code().unwrap_or_default()
+		);
+	}
+
+	Ok(true)
+}
+
+fn remove_input(input_path: &Path) -> anyhow::Result<()> {
+	match fs::remove_file(input_path) {
+		Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+			Err(err).with_context(|| format!("failed to remove {}", input_path.display()))
+		}
+		_ => Ok(()),
+	}
+}
+
+/// Links `input_path` to `target` with a relative symlink, so that the workspace can be moved.
+/// Elsewhere than on unix it's a hard link, symlinks need extra privileges on Windows.
+fn link(input_path: &Path, target: &Path) -> anyhow::Result<()> {
+	#[cfg(unix)]
+	let linked = {
+		let from = std::path::absolute(input_path)?;
+		let to = std::path::absolute(target)?;
+		// SAFETY: input_path is a file, it has a parent
+		std::os::unix::fs::symlink(relative_path(from.parent().unwrap(), &to), input_path)
+	};
+	#[cfg(not(unix))]
+	let linked = fs::hard_link(target, input_path);
+
+	linked.with_context(|| {
+		format!(
+			"failed to link {} to {}",
+			input_path.display(),
+			target.display()
+		)
+	})
+}
+
+/// Path of `to` relative to the directory `from`, both absolute.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn relative_path(from: &Path, to: &Path) -> PathBuf {
+	let from = from
+		.components()
+		.filter(|component| *component != Component::CurDir)
+		.collect::<Vec<_>>();
+	let to = to
+		.components()
+		.filter(|component| *component != Component::CurDir)
+		.collect::<Vec<_>>();
+	let common = from
+		.iter()
+		.zip(&to)
+		.take_while(|(from, to)| from == to)
+		.count();
+
+	let mut path = PathBuf::new();
+	for _ in common..from.len() {
+		path.push("..");
+	}
+	path.extend(&to[common..]);
+	path
+}
+
+pub fn sha256_hex(bytes: impl AsRef<[u8]>) -> String {
+	Sha256::digest(bytes)
+		.iter()
+		.map(|byte| format!("{:02x}", byte))
+		.collect()
+}
+
+/// Identifies the session without storing the cookie itself.
+pub fn session_fingerprint(session_cookie: &str) -> String {
+	sha256_hex(session_cookie)[..16].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Cached, InputMeta, Storage};
+	use std::fs;
+	use std::path::Path;
+
+	#[test]
+	fn store_and_lookup() {
+		let dir = tempfile::tempdir().unwrap();
+		assert_eq!(super::lookup(dir.path()).unwrap(), Cached::Missing);
+
+		fs::write(dir.path().join("input.txt"), "1\n").unwrap();
+		assert_eq!(super::lookup(dir.path()).unwrap(), Cached::Untracked);
+
+		let meta = InputMeta {
+			year: 2022,
+			day: 1,
+			fetched_at: 1669870800,
+			sha256: super::sha256_hex("1\n2\n"),
+			session: super::session_fingerprint("cookie"),
+		};
+		super::store(dir.path(), None, &meta, "1\n2\n").unwrap();
+		assert_eq!(
+			super::lookup(dir.path()).unwrap(),
+			Cached::Fetched(meta.clone())
+		);
+
+		fs::write(dir.path().join("input.txt"), "1\n").unwrap();
+		assert_eq!(super::lookup(dir.path()).unwrap(), Cached::Modified(meta));
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn stored_elsewhere() {
+		let root = tempfile::tempdir().unwrap();
+		let crate_dir = root.path().join("day_1");
+		fs::create_dir(&crate_dir).unwrap();
+		let stored_at = Storage::Cache
+			.outside_crate(root.path(), Path::new("day_1"))
+			.unwrap();
+		assert_eq!(
+			stored_at,
+			root.path().join(".aqa_aoc/inputs/day_1/input.txt")
+		);
+		assert_eq!(
+			Storage::InTree.outside_crate(root.path(), Path::new("day_1")),
+			None
+		);
+
+		fs::write(crate_dir.join("input.txt"), "1\n").unwrap();
+		assert!(super::move_input(&crate_dir, &stored_at).unwrap());
+		assert!(!super::move_input(&crate_dir, &stored_at).unwrap());
+		assert_eq!(
+			fs::read_link(crate_dir.join("input.txt")).unwrap(),
+			Path::new("../.aqa_aoc/inputs/day_1/input.txt")
+		);
+		assert_eq!(super::lookup(&crate_dir).unwrap(), Cached::Untracked);
+
+		let meta = InputMeta {
+			year: 2022,
+			day: 1,
+			fetched_at: 1669870800,
+			sha256: super::sha256_hex("2\n"),
+			session: super::session_fingerprint("cookie"),
+		};
+		super::store(&crate_dir, Some(&stored_at), &meta, "2\n").unwrap();
+		assert_eq!(fs::read_to_string(&stored_at).unwrap(), "2\n");
+		assert_eq!(super::lookup(&crate_dir).unwrap(), Cached::Fetched(meta));
+	}
+
+	#[test]
+	fn relative_path() {
+		assert_eq!(
+			super::relative_path(
+				Path::new("/ws/year_2022/day_1"),
+				Path::new("/ws/.aqa_aoc/inputs/year_2022/day_1/input.txt")
+			),
+			Path::new("../../.aqa_aoc/inputs/year_2022/day_1/input.txt")
+		);
+	}
+
+	#[test]
+	fn sha256() {
+		assert_eq!(
+			super::sha256_hex("abc"),
+			"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+		);
+	}
+}
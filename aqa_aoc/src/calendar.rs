@@ -0,0 +1,65 @@
+//! The calendar page of a year, which shows the stars of the logged in user.
+
+use crate::config::AocCfg;
+use crate::error::AocError;
+use crate::html::{self, Token};
+use crate::session;
+use std::collections::BTreeMap;
+
+/// [stars] of `year` from the calendar of the user whose session is in `cfg`.
+pub fn user_stars(cfg: &AocCfg, year: u32) -> anyhow::Result<BTreeMap<u32, u8>> {
+	let page = cfg.client()?.calendar(year)?;
+	if session::logged_in_user(&page).is_none() {
+		return Err(AocError::BadSession {
+			origin: Some(cfg.session()?.source.to_string()),
+		}
+		.into());
+	}
+	Ok(stars(&page))
+}
+
+/// Stars of every unlocked day. AoC marks the days with one star `calendar-complete` and the days
+/// with both `calendar-verycomplete`.
+pub fn stars(page: &str) -> BTreeMap<u32, u8> {
+	html::tokenize(page)
+		.iter()
+		.filter(|token| token.is_start("a"))
+		.filter_map(|token| {
+			let classes = token.attr("class")?;
+			let day = classes
+				.split_whitespace()
+				.find_map(|class| class.strip_prefix("calendar-day"))?
+				.parse::<u32>()
+				.ok()?;
+			Some((day, day_stars(token)))
+		})
+		.collect()
+}
+
+fn day_stars(token: &Token<'_>) -> u8 {
+	if token.has_class("calendar-verycomplete") {
+		2
+	} else if token.has_class("calendar-complete") {
+		1
+	} else {
+		0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn stars() {
+		let page = r#"<pre class="calendar">
+<a aria-label="Day 2, one star" href="/2022/day/2" class="calendar-day2 calendar-complete">  <span class="calendar-day"> 2</span> <span class="calendar-mark-complete">*</span></a>
+<a aria-label="Day 1, two stars" href="/2022/day/1" class="calendar-day1 calendar-verycomplete">  <span class="calendar-day"> 1</span></a>
+<a aria-label="Day 3" href="/2022/day/3" class="calendar-day3">  <span class="calendar-day"> 3</span></a>
+<span aria-hidden="true" class="calendar-day4">       </span>
+</pre>"#;
+
+		assert_eq!(
+			super::stars(page).into_iter().collect::<Vec<_>>(),
+			vec![(1, 2), (2, 1), (3, 0)]
+		);
+	}
+}
@@ -0,0 +1,684 @@
+use anyhow::{anyhow, bail};
+use aqa_aoc::{date, error, leaderboard};
+
+pub const FIRST_YEAR: u32 = 2015;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CliApp {
+	pub day: Option<u32>,
+	pub year: Option<u32>,
+	pub command: CliCommand,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CliCommand {
+	/// No subcommand: create the day crate if needed and fetch everything for it
+	NewAndFetch {
+		example: Option<usize>,
+		force: bool,
+		wait: bool,
+	},
+	New,
+	Fetch {
+		example: Option<usize>,
+		force: bool,
+		wait: bool,
+	},
+	Run {
+		part: Option<u32>,
+		release: bool,
+	},
+	Watch {
+		release: bool,
+	},
+	Submit {
+		part: u32,
+		answer: String,
+		force: bool,
+	},
+	Status {
+		tests: bool,
+		offline: bool,
+	},
+	Whoami,
+	MigrateInputs,
+	Leaderboard {
+		id: String,
+		format: leaderboard::Format,
+	},
+	Doctor {
+		fix: bool,
+	},
+	Archive {
+		to: Option<String>,
+		remove: bool,
+	},
+	Restore {
+		bundle: String,
+	},
+	Help {
+		command: Option<String>,
+	},
+}
+
+struct CommandSpec {
+	name: &'static str,
+	about: &'static str,
+	usage: &'static str,
+	/// Flags taking a value, as `--flag value` or `--flag=value`
+	options: &'static [&'static str],
+	switches: &'static [&'static str],
+	positional: Option<&'static str>,
+}
+
+const DEFAULT_COMMAND: CommandSpec = CommandSpec {
+	name: "",
+	about: "Create the day crate if it doesn't exist yet, then fetch its input and puzzle",
+	usage: "aqa_aoc [--day N] [--year YYYY] [--example N] [--force] [--wait]",
+	options: &["--day", "--year", "--example"],
+	switches: &["--force", "--wait"],
+	positional: None,
+};
+
+const COMMANDS: &[CommandSpec] = &[
+	CommandSpec {
+		name: "new",
+		about: "Create the day crate and add it to the workspace, without touching the network",
+		usage: "aqa_aoc new [--day N] [--year YYYY]",
+		options: &["--day", "--year"],
+		switches: &[],
+		positional: None,
+	},
+	CommandSpec {
+		name: "fetch",
+		about: "Download the input and puzzle description of an existing day crate",
+		usage: "aqa_aoc fetch [--day N] [--year YYYY] [--example N] [--force] [--wait]",
+		options: &["--day", "--year", "--example"],
+		switches: &["--force", "--wait"],
+		positional: None,
+	},
+	CommandSpec {
+		name: "run",
+		about: "Build and run the solution of a day, timing each part and checking answers.toml",
+		usage: "aqa_aoc run [--day N] [--year YYYY] [--part P] [--release]",
+		options: &["--day", "--year", "--part"],
+		switches: &["--release"],
+		positional: None,
+	},
+	CommandSpec {
+		name: "watch",
+		about: "Run the tests, then the solution, every time the day's sources or input change",
+		usage: "aqa_aoc watch [--day N] [--year YYYY] [--release]",
+		options: &["--day", "--year"],
+		switches: &["--release"],
+		positional: None,
+	},
+	CommandSpec {
+		name: "submit",
+		about: "Submit an answer",
+		usage: "aqa_aoc submit --day N --part P [--year YYYY] [--force] <answer>",
+		options: &["--day", "--year", "--part"],
+		switches: &["--force"],
+		positional: Some("answer"),
+	},
+	CommandSpec {
+		name: "status",
+		about: "Show the day crates of the workspace, their inputs, tests, answers and stars",
+		usage: "aqa_aoc status [--year YYYY] [--tests] [--offline]",
+		options: &["--year"],
+		switches: &["--tests", "--offline"],
+		positional: None,
+	},
+	CommandSpec {
+		name: "whoami",
+		about: "Check the session cookie and show who it belongs to",
+		usage: "aqa_aoc whoami [--year YYYY]",
+		options: &["--year"],
+		switches: &[],
+		positional: None,
+	},
+	CommandSpec {
+		name: "migrate-inputs",
+		about: "Move the inputs out of git, to the gitignored .aqa_aoc directory",
+		usage: "aqa_aoc migrate-inputs",
+		options: &[],
+		switches: &[],
+		positional: None,
+	},
+	CommandSpec {
+		name: "leaderboard",
+		about: "Show a private leaderboard, with the star times of every day",
+		usage: "aqa_aoc leaderboard [--year YYYY] [--day N] [--format table|json|csv] <id>",
+		options: &["--year", "--day", "--format"],
+		switches: &[],
+		positional: Some("id"),
+	},
+	CommandSpec {
+		name: "doctor",
+		about: "Check that workspace members, day crates and inputs agree",
+		usage: "aqa_aoc doctor [--fix] [--year YYYY]",
+		options: &["--year"],
+		switches: &["--fix"],
+		positional: None,
+	},
+	CommandSpec {
+		name: "archive",
+		about: "Copy the day crates of a year to a directory, with their answers, input hashes and timings",
+		usage: "aqa_aoc archive [--year YYYY] [--to DIR] [--remove]",
+		options: &["--year", "--to"],
+		switches: &["--remove"],
+		positional: None,
+	},
+	CommandSpec {
+		name: "restore",
+		about: "Put the day crates of an archive back into the workspace",
+		usage: "aqa_aoc restore <dir>",
+		options: &[],
+		switches: &[],
+		positional: Some("dir"),
+	},
+	CommandSpec {
+		name: "help",
+		about: "Show help for a command",
+		usage: "aqa_aoc help [command]",
+		options: &[],
+		switches: &[],
+		positional: Some("command"),
+	},
+];
+
+/// Every flag: its name, how it's written in the help and what it does.
+const FLAGS: &[(&str, &str, &str)] = &[
+	(
+		"--day",
+		"--day N",
+		"Day of the puzzle, 1-25. Defaults to the day the workspace is at",
+	),
+	(
+		"--year",
+		"--year YYYY",
+		"Year of the puzzle, overrides `year` from aoc_cfg.toml",
+	),
+	("--part", "--part P", "Puzzle part, 1 or 2"),
+	(
+		"--example",
+		"--example N",
+		"Which code block of the puzzle is the example, asked when not given",
+	),
+	(
+		"--force",
+		"--force",
+		"Download the input again even if it was downloaded before, or submit an\n                    answer that answers.toml says is wrong",
+	),
+	(
+		"--wait",
+		"--wait",
+		"Wait for the puzzle to unlock, then retry until the input is available",
+	),
+	("--release", "--release", "Build in release mode"),
+	("--fix", "--fix", "Repair the problems found"),
+	(
+		"--tests",
+		"--tests",
+		"Run the tests of every day to show how many pass",
+	),
+	("--offline", "--offline", "Don't fetch the stars from the calendar"),
+	(
+		"--format",
+		"--format F",
+		"Output of the leaderboard: table (default), json or csv",
+	),
+	("--to", "--to DIR", "Directory to archive to, aoc_YYYY by default"),
+	(
+		"--remove",
+		"--remove",
+		"Remove the archived day crates from the workspace",
+	),
+	("-h", "-h, --help", "Show help"),
+	(
+		"--",
+		"--",
+		"End of the flags, for an answer that starts with `-`",
+	),
+];
+
+/// The flags `spec` takes, or all of them.
+fn flags_help(spec: Option<&CommandSpec>) -> String {
+	let flags = FLAGS
+		.iter()
+		.filter(|(name, _, _)| match (spec, *name) {
+			(None, _) | (_, "-h") => true,
+			(Some(spec), "--") => spec.positional.is_some(),
+			(Some(spec), name) => spec.options.contains(&name) || spec.switches.contains(&name),
+		})
+		.map(|(_, usage, about)| format!("    {:<16}{}", usage, about))
+		.collect::<Vec<_>>();
+	format!("Flags:\n{}", flags.join("\n"))
+}
+
+pub fn help(command: Option<&str>) -> String {
+	match command.and_then(|name| COMMANDS.iter().find(|spec| spec.name == name)) {
+		Some(spec) => format!(
+			"{}\n\nUsage: {}\n\n{}",
+			spec.about,
+			spec.usage,
+			flags_help(Some(spec))
+		),
+		None => {
+			let commands = COMMANDS
+				.iter()
+				.map(|spec| format!("    {:<16}{}\n", spec.name, spec.about))
+				.collect::<String>();
+			let exit_codes = error::EXIT_CODES
+				.iter()
+				.map(|(code, about)| format!("    {:<8}{}\n", code, about))
+				.collect::<String>();
+			format!(
+				"Advent of Code helper\n\nUsage: {}\n       aqa_aoc <command> [flags]\n\n{}\n\nCommands:\n{}\nExit codes:\n{}",
+				DEFAULT_COMMAND.usage,
+				flags_help(None),
+				commands,
+				exit_codes
+			)
+		}
+	}
+}
+
+impl CliApp {
+	pub fn from_args() -> anyhow::Result<CliApp> {
+		CliApp::parse(std::env::args().skip(1))
+	}
+
+	pub fn parse(args: impl IntoIterator<Item = String>) -> anyhow::Result<CliApp> {
+		let mut args = args.into_iter().peekable();
+
+		let spec = match args.next_if(|arg| !arg.starts_with('-')) {
+			Some(name) => COMMANDS
+				.iter()
+				.find(|spec| spec.name == name)
+				.ok_or_else(|| anyhow!("unknown command `{}`, see `aqa_aoc help`", name))?,
+			None => &DEFAULT_COMMAND,
+		};
+
+		let mut options = Vec::new();
+		let mut switches = Vec::new();
+		let mut positional = None;
+		let mut flags_ended = false;
+
+		while let Some(arg) = args.next() {
+			if !flags_ended && arg == "--" {
+				flags_ended = true;
+				continue;
+			}
+
+			if !flags_ended && (arg == "-h" || arg == "--help") {
+				return Ok(CliApp {
+					day: None,
+					year: None,
+					command: CliCommand::Help {
+						command: Some(spec.name.to_string()).filter(|name| !name.is_empty()),
+					},
+				});
+			}
+
+			if !flags_ended && is_flag(&arg) {
+				let (flag, value) = match arg.split_once('=') {
+					Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+					None => (arg, None),
+				};
+
+				if spec.options.contains(&flag.as_str()) {
+					let value = match value {
+						Some(value) => value,
+						None => args
+							.next()
+							.ok_or_else(|| anyhow!("missing value for {}", flag))?,
+					};
+					options.push((flag, value));
+				} else if spec.switches.contains(&flag.as_str()) && value.is_none() {
+					switches.push(flag);
+				} else {
+					bail!("unknown flag {} for {}", flag, command_name(spec));
+				}
+			} else if spec.positional.is_some() && positional.is_none() {
+				positional = Some(arg);
+			} else {
+				bail!("unexpected argument `{}` for {}", arg, command_name(spec));
+			}
+		}
+
+		let option = |name: &str| {
+			options
+				.iter()
+				.rev()
+				.find(|(flag, _)| flag == name)
+				.map(|(_, value)| value.as_str())
+		};
+		let switch = |name: &str| switches.iter().any(|flag| flag == name);
+
+		let day = option("--day").map(parse_day).transpose()?;
+		let year = option("--year").map(parse_year).transpose()?;
+		let example = option("--example")
+			.map(|example| {
+				example
+					.parse::<usize>()
+					.map_err(|_| anyhow!("invalid example number `{}`", example))
+			})
+			.transpose()?;
+
+		let command = match spec.name {
+			"" => CliCommand::NewAndFetch {
+				example,
+				force: switch("--force"),
+				wait: switch("--wait"),
+			},
+			"new" => CliCommand::New,
+			"fetch" => CliCommand::Fetch {
+				example,
+				force: switch("--force"),
+				wait: switch("--wait"),
+			},
+			"run" => CliCommand::Run {
+				part: option("--part").map(parse_part).transpose()?,
+				release: switch("--release"),
+			},
+			"watch" => CliCommand::Watch {
+				release: switch("--release"),
+			},
+			"submit" => {
+				let part = option("--part")
+					.map(parse_part)
+					.transpose()?
+					.ok_or_else(|| anyhow!("submit requires --part"))?;
+				if day.is_none() {
+					bail!("submit requires --day");
+				}
+				CliCommand::Submit {
+					part,
+					answer: positional.ok_or_else(|| anyhow!("submit requires an answer"))?,
+					force: switch("--force"),
+				}
+			}
+			"status" => CliCommand::Status {
+				tests: switch("--tests"),
+				offline: switch("--offline"),
+			},
+			"whoami" => CliCommand::Whoami,
+			"migrate-inputs" => CliCommand::MigrateInputs,
+			"leaderboard" => {
+				let id = positional
+					.ok_or_else(|| anyhow!("leaderboard requires the id of a leaderboard"))?;
+				if id.is_empty() || !id.bytes().all(|b| b.is_ascii_digit()) {
+					bail!("leaderboard id must be a number, got `{}`", id);
+				}
+				CliCommand::Leaderboard {
+					id,
+					format: option("--format")
+						.map(parse_format)
+						.transpose()?
+						.unwrap_or(leaderboard::Format::Table),
+				}
+			}
+			"doctor" => CliCommand::Doctor {
+				fix: switch("--fix"),
+			},
+			"archive" => CliCommand::Archive {
+				to: option("--to").map(str::to_string),
+				remove: switch("--remove"),
+			},
+			"restore" => CliCommand::Restore {
+				bundle: positional
+					.ok_or_else(|| anyhow!("restore requires the directory of an archive"))?,
+			},
+			"help" => {
+				if let Some(name) = &positional {
+					if !COMMANDS
+						.iter()
+						.any(|spec| !spec.name.is_empty() && spec.name == name)
+					{
+						bail!("unknown command `{}`, see `aqa_aoc help`", name);
+					}
+				}
+				CliCommand::Help {
+					command: positional,
+				}
+			}
+			name => unreachable!("command `{}` is not handled", name),
+		};
+
+		Ok(CliApp { day, year, command })
+	}
+}
+
+/// Negative numbers, like an answer of `-5`, are values rather than flags.
+fn is_flag(arg: &str) -> bool {
+	arg.starts_with('-') && !arg[1..].starts_with(|c: char| c.is_ascii_digit())
+}
+
+fn command_name(spec: &CommandSpec) -> String {
+	if spec.name.is_empty() {
+		"aqa_aoc".to_string()
+	} else {
+		format!("`{}`", spec.name)
+	}
+}
+
+fn parse_day(day: &str) -> anyhow::Result<u32> {
+	match day.parse::<u32>() {
+		Ok(day) if (1..=25).contains(&day) => Ok(day),
+		_ => Err(anyhow!("day must be between 1 and 25, got `{}`", day)),
+	}
+}
+
+fn parse_year(year: &str) -> anyhow::Result<u32> {
+	let current_year = date::current_year();
+	match year.parse::<u32>() {
+		Ok(year) if year >= FIRST_YEAR && i64::from(year) <= current_year => Ok(year),
+		_ => Err(anyhow!(
+			"year must be between {} and {}, got `{}`",
+			FIRST_YEAR,
+			current_year,
+			year
+		)),
+	}
+}
+
+fn parse_part(part: &str) -> anyhow::Result<u32> {
+	match part.parse::<u32>() {
+		Ok(part) if (1..=2).contains(&part) => Ok(part),
+		_ => Err(anyhow!("part must be 1 or 2, got `{}`", part)),
+	}
+}
+
+fn parse_format(format: &str) -> anyhow::Result<leaderboard::Format> {
+	match format {
+		"table" => Ok(leaderboard::Format::Table),
+		"json" => Ok(leaderboard::Format::Json),
+		"csv" => Ok(leaderboard::Format::Csv),
+		_ => Err(anyhow!(
+			"format must be table, json or csv, got `{}`",
+			format
+		)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{CliApp, CliCommand};
+	use aqa_aoc::leaderboard;
+
+	fn parse(args: &[&str]) -> anyhow::Result<CliApp> {
+		CliApp::parse(args.iter().map(|arg| arg.to_string()))
+	}
+
+	#[test]
+	fn default_command() {
+		assert_eq!(
+			parse(&[]).unwrap(),
+			CliApp {
+				day: None,
+				year: None,
+				command: CliCommand::NewAndFetch {
+					example: None,
+					force: false,
+					wait: false
+				},
+			}
+		);
+		assert_eq!(
+			parse(&["--day=3", "--example", "2"]).unwrap(),
+			CliApp {
+				day: Some(3),
+				year: None,
+				command: CliCommand::NewAndFetch {
+					example: Some(2),
+					force: false,
+					wait: false
+				},
+			}
+		);
+	}
+
+	#[test]
+	fn subcommands() {
+		assert_eq!(
+			parse(&["new", "--day", "7"]).unwrap().command,
+			CliCommand::New
+		);
+		assert_eq!(
+			parse(&["fetch", "--force", "--wait"]).unwrap().command,
+			CliCommand::Fetch {
+				example: None,
+				force: true,
+				wait: true
+			}
+		);
+		assert_eq!(
+			parse(&["run", "--release"]).unwrap().command,
+			CliCommand::Run {
+				part: None,
+				release: true
+			}
+		);
+		assert_eq!(
+			parse(&["run", "--day", "4", "--part=2"]).unwrap().command,
+			CliCommand::Run {
+				part: Some(2),
+				release: false
+			}
+		);
+		assert_eq!(
+			parse(&["watch", "--day", "3"]).unwrap(),
+			CliApp {
+				day: Some(3),
+				year: None,
+				command: CliCommand::Watch { release: false },
+			}
+		);
+		assert_eq!(
+			parse(&["submit", "--day=1", "--part", "2", "--year", "2021", "1234"]).unwrap(),
+			CliApp {
+				day: Some(1),
+				year: Some(2021),
+				command: CliCommand::Submit {
+					part: 2,
+					answer: "1234".to_string(),
+					force: false
+				},
+			}
+		);
+		assert_eq!(
+			parse(&["submit", "--day", "3", "--part", "1", "-5"])
+				.unwrap()
+				.command,
+			CliCommand::Submit {
+				part: 1,
+				answer: "-5".to_string(),
+				force: false
+			}
+		);
+		assert_eq!(
+			parse(&["submit", "--day", "3", "--part", "1", "--", "--x"])
+				.unwrap()
+				.command,
+			CliCommand::Submit {
+				part: 1,
+				answer: "--x".to_string(),
+				force: false
+			}
+		);
+		assert_eq!(
+			parse(&["doctor", "--fix"]).unwrap().command,
+			CliCommand::Doctor { fix: true }
+		);
+		assert_eq!(
+			parse(&["leaderboard", "--format=csv", "123456"])
+				.unwrap()
+				.command,
+			CliCommand::Leaderboard {
+				id: "123456".to_string(),
+				format: leaderboard::Format::Csv
+			}
+		);
+		assert_eq!(
+			parse(&["archive", "--year=2022", "--to", "../aoc_2022", "--remove"])
+				.unwrap()
+				.command,
+			CliCommand::Archive {
+				to: Some("../aoc_2022".to_string()),
+				remove: true
+			}
+		);
+		assert_eq!(
+			parse(&["restore", "../aoc_2022"]).unwrap().command,
+			CliCommand::Restore {
+				bundle: "../aoc_2022".to_string()
+			}
+		);
+		assert_eq!(
+			parse(&["fetch", "--help"]).unwrap().command,
+			CliCommand::Help {
+				command: Some("fetch".to_string())
+			}
+		);
+	}
+
+	#[test]
+	fn validation() {
+		assert!(parse(&["--day=0"]).is_err());
+		assert!(parse(&["--day=26"]).is_err());
+		assert!(parse(&["--year=2014"]).is_err());
+		assert!(parse(&["--year=9999"]).is_err());
+		assert!(parse(&["--bogus"]).is_err());
+		assert!(parse(&["status", "--day=1"]).is_err());
+		assert!(parse(&["new", "extra"]).is_err());
+		assert!(parse(&["frobnicate"]).is_err());
+		assert!(parse(&["submit", "--day=1", "--part=3", "1"]).is_err());
+		assert!(parse(&["submit", "--part=1", "1"]).is_err());
+		assert!(parse(&["submit", "--day=1", "--part=1"]).is_err());
+		assert!(parse(&["leaderboard"]).is_err());
+		assert!(parse(&["leaderboard", "abc"]).is_err());
+		assert!(parse(&["leaderboard", "--format=xml", "1"]).is_err());
+		assert_eq!(
+			parse(&["help", "frobnicate"]).unwrap_err().to_string(),
+			"unknown command `frobnicate`, see `aqa_aoc help`"
+		);
+		assert!(parse(&["--day", "-3"]).is_err());
+	}
+
+	#[test]
+	fn help() {
+		assert!(super::help(None).contains("    submit          Submit an answer\n"));
+		assert!(super::help(None).contains("    3       the puzzle is not unlocked yet\n"));
+		assert!(super::help(Some("run"))
+			.contains("Usage: aqa_aoc run [--day N] [--year YYYY] [--part P] [--release]"));
+
+		let run = super::help(Some("run"));
+		assert!(run.contains("    --part P        Puzzle part, 1 or 2\n"));
+		assert!(run.contains("    -h, --help      Show help"));
+		assert!(!run.contains("--fix"));
+		assert!(!run.contains("End of the flags"));
+		assert!(super::help(Some("submit")).contains("End of the flags"));
+		assert!(super::help(None).contains("    --remove        "));
+	}
+}
@@ -0,0 +1,187 @@
+//! Requests to the AoC servers.
+
+use crate::error::{self, AocError};
+use crate::session;
+use crate::submit::{self, Outcome};
+use crate::throttle::Throttle;
+use ureq::Cookie;
+
+pub static AOC_ADDR: &str = "https://adventofcode.com";
+
+pub struct AocClient {
+	agent: ureq::Agent,
+	base_url: String,
+	throttle: Option<Throttle>,
+	log_requests: bool,
+}
+
+impl AocClient {
+	/// A client that logs in with `session_cookie`. `base_url` is [AOC_ADDR], or a stand-in for it.
+	pub fn new(base_url: &str, session_cookie: &str) -> AocClient {
+		let base_url = base_url.trim_end_matches('/').to_string();
+		let (secure, host) = match base_url.split_once("://") {
+			Some((scheme, rest)) => (scheme == "https", rest),
+			None => (true, base_url.as_str()),
+		};
+		let host = host.split('/').next().unwrap_or_default();
+		let domain = host.split(':').next().unwrap_or_default();
+
+		let cookie = Cookie::build("session", session_cookie.to_string())
+			.domain(domain.to_string())
+			.path("/")
+			.secure(secure)
+			.finish();
+
+		let mut agent = ureq::agent();
+		agent.set_cookie(cookie);
+		agent.set("User-Agent", concat!("aqa_aoc/", env!("CARGO_PKG_VERSION")));
+
+		AocClient {
+			agent,
+			base_url,
+			throttle: None,
+			log_requests: false,
+		}
+	}
+
+	pub fn user_agent(mut self, user_agent: &str) -> AocClient {
+		self.agent.set("User-Agent", user_agent);
+		self
+	}
+
+	/// Keeps the delay of `throttle` between requests.
+	pub fn throttle(mut self, throttle: Throttle) -> AocClient {
+		self.throttle = Some(throttle);
+		self
+	}
+
+	/// Prints the address of every request to stderr.
+	pub fn log_requests(mut self, log_requests: bool) -> AocClient {
+		self.log_requests = log_requests;
+		self
+	}
+
+	pub fn base_url(&self) -> &str {
+		&self.base_url
+	}
+
+	pub fn input(&self, year: u32, day: u32) -> anyhow::Result<String> {
+		Ok(self.get(&format!("/{}/day/{}/input", year, day))?)
+	}
+
+	/// The puzzle page, see [crate::puzzle] and [crate::examples] for what to do with it.
+	pub fn puzzle(&self, year: u32, day: u32) -> anyhow::Result<String> {
+		Ok(self.get(&format!("/{}/day/{}", year, day))?)
+	}
+
+	/// The calendar page of `year`, see [crate::calendar].
+	pub fn calendar(&self, year: u32) -> anyhow::Result<String> {
+		Ok(self.get(&format!("/{}", year))?)
+	}
+
+	/// Name of the user the session belongs to, `None` when AoC doesn't accept the session.
+	pub fn whoami(&self, year: u32) -> anyhow::Result<Option<String>> {
+		Ok(session::logged_in_user(&self.calendar(year)?))
+	}
+
+	/// JSON of a private leaderboard, see [crate::leaderboard]. AoC asks not to fetch it more than
+	/// every [crate::leaderboard::CACHE_FOR].
+	pub fn leaderboard(&self, year: u32, id: &str) -> anyhow::Result<String> {
+		Ok(self.get(&format!("/{}/leaderboard/private/view/{}.json", year, id))?)
+	}
+
+	pub fn submit(&self, year: u32, day: u32, part: u32, answer: &str) -> anyhow::Result<Outcome> {
+		let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+		self.before_request(&url);
+		let response = self
+			.agent
+			.post(&url)
+			.send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+		submit::parse_outcome(&error::read_response(&url, response)?)
+	}
+
+	fn get(&self, path: &str) -> Result<String, AocError> {
+		let url = format!("{}{}", self.base_url, path);
+		self.before_request(&url);
+		let response = self.agent.get(&url).call();
+
+		error::read_response(&url, response)
+	}
+
+	fn before_request(&self, url: &str) {
+		if self.log_requests {
+			eprintln!("Request addr: {}", url);
+		}
+		if let Some(throttle) = &self.throttle {
+			if let Err(err) = throttle.wait() {
+				eprintln!(
+					"Failed to record the request time in {}: {}",
+					throttle.path.display(),
+					err
+				);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::AocClient;
+	use crate::submit::Outcome;
+	use std::io::{BufRead, BufReader, Read, Write};
+	use std::net::TcpListener;
+
+	#[test]
+	fn submit_to_stand_in_server() {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let addr = format!("http://{}", listener.local_addr().unwrap());
+
+		let server = std::thread::spawn(move || {
+			let (stream, _) = listener.accept().unwrap();
+			let mut reader = BufReader::new(stream);
+
+			let mut request_line = String::new();
+			reader.read_line(&mut request_line).unwrap();
+
+			let mut content_length = 0;
+			let mut cookie = String::new();
+			loop {
+				let mut line = String::new();
+				reader.read_line(&mut line).unwrap();
+				if line == "\r\n" {
+					break;
+				}
+				if let Some((name, value)) = line.split_once(':') {
+					if name.eq_ignore_ascii_case("content-length") {
+						content_length = value.trim().parse().unwrap();
+					} else if name.eq_ignore_ascii_case("cookie") {
+						cookie = value.trim().to_string();
+					}
+				}
+			}
+			let mut body = vec![0; content_length];
+			reader.read_exact(&mut body).unwrap();
+
+			let response = "<html><body><main><article><p>That's not the right answer; your answer is too low.</p></article></main></body></html>";
+			write!(
+				reader.get_mut(),
+				"HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+				response.len(),
+				response
+			)
+			.unwrap();
+
+			(request_line, cookie, String::from_utf8(body).unwrap())
+		});
+
+		let client = AocClient::new(&format!("{}/", addr), "cookie");
+		let outcome = client.submit(2022, 1, 2, "1234").unwrap();
+		assert_eq!(outcome, Outcome::TooLow);
+
+		let (request_line, cookie, body) = server.join().unwrap();
+		assert_eq!(request_line.trim_end(), "POST /2022/day/1/answer HTTP/1.1");
+		assert_eq!(cookie, "session=cookie");
+		assert_eq!(body, "level=2&answer=1234");
+	}
+}
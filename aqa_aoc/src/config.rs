@@ -0,0 +1,201 @@
+//! aoc_cfg.toml, in the root of the workspace.
+
+use crate::cache::Storage;
+use crate::client::{AocClient, AOC_ADDR};
+use crate::date;
+use crate::dependencies::{self, Dependencies};
+use crate::error::AocError;
+use crate::layout::{DayCrate, Layout};
+use crate::session;
+use crate::throttle::{self, Throttle};
+use anyhow::Context;
+use serde::Deserialize;
+use std::cell::OnceCell;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub static CFG_FILE: &str = "aoc_cfg.toml";
+
+/// Overrides `base_url` from aoc_cfg.toml, mostly useful for pointing the tool at a mock server.
+pub static BASE_URL_ENV: &str = "AQA_AOC_BASE_URL";
+
+/// Tool state that isn't part of any crate, relative to the workspace root.
+pub static STATE_DIR: &str = ".aqa_aoc";
+
+#[derive(Deserialize)]
+pub struct AocCfg {
+	pub year: u32,
+	session_cookie: Option<String>,
+	/// Command that prints the session cookie, e.g. a password manager lookup
+	session_command: Option<String>,
+	/// File with the session cookie, `~/.config/aqa_aoc/session` is used when nothing is set
+	session_file: Option<PathBuf>,
+	/// Where the day crates go, `flat` (the default) or `by_year`
+	#[serde(default)]
+	pub layout: Layout,
+	base_url: Option<String>,
+	/// Directory with the template for new day crates, the built-in one is used if not set
+	pub template_dir: Option<PathBuf>,
+	/// Where the inputs are kept, `in_tree` (the default) or `cache` to keep them out of git
+	#[serde(default)]
+	pub inputs: Storage,
+	/// Minimum delay between two requests to the AoC servers
+	request_delay_ms: Option<u64>,
+	/// Dependencies of new day crates, like `[dependencies]` of a Cargo.toml but with paths relative
+	/// to the workspace root. Defaults to anyhow, itertools, regex and nom, and aoc_common when the
+	/// workspace has it.
+	dependencies: Option<Dependencies>,
+	/// Added to the User-Agent header so that the AoC admins can reach you, e.g. an email address
+	contact: Option<String>,
+	/// Workspace root
+	#[serde(skip)]
+	root: PathBuf,
+	#[serde(skip)]
+	session: OnceCell<session::Session>,
+	#[serde(skip)]
+	client: OnceCell<AocClient>,
+	/// User the session belongs to, once checked
+	#[serde(skip)]
+	user: OnceCell<String>,
+}
+
+/// The part of aoc_cfg.toml that commands which don't download anything need. They work without
+/// the file too.
+#[derive(Deserialize, Default)]
+struct WorkspaceCfg {
+	year: Option<u32>,
+	#[serde(default)]
+	layout: Layout,
+}
+
+impl AocCfg {
+	/// Reads aoc_cfg.toml of the workspace in `root`. `year` overrides the year of the file.
+	pub fn load(root: &Path, year: Option<u32>) -> anyhow::Result<AocCfg> {
+		let mut cfg: AocCfg = toml::from_slice(
+			&std::fs::read(root.join(CFG_FILE)).context("failed to read aoc_cfg.toml")?,
+		)
+		.context("failed to parse aoc_cfg.toml")?;
+		if let Some(year) = year {
+			cfg.year = year;
+		}
+		cfg.root = root.to_path_buf();
+
+		Ok(cfg)
+	}
+
+	pub fn day_crate(&self, day: u32) -> DayCrate {
+		self.layout.day_crate(self.year, day)
+	}
+
+	pub fn dependencies(&self) -> Dependencies {
+		self.dependencies
+			.clone()
+			.unwrap_or_else(|| dependencies::defaults(&self.root))
+	}
+
+	pub fn session(&self) -> anyhow::Result<&session::Session> {
+		if let Some(session) = self.session.get() {
+			return Ok(session);
+		}
+
+		let session = session::resolve(
+			std::env::var(session::SESSION_ENV).ok(),
+			session::Settings {
+				cookie: self.session_cookie.as_deref(),
+				command: self.session_command.as_deref(),
+				file: self.session_file.as_deref(),
+			},
+		)?;
+		Ok(self.session.get_or_init(|| session))
+	}
+
+	pub fn base_url(&self) -> String {
+		let base_url = match std::env::var(BASE_URL_ENV) {
+			Ok(base_url) if !base_url.is_empty() => base_url,
+			_ => self
+				.base_url
+				.clone()
+				.unwrap_or_else(|| AOC_ADDR.to_string()),
+		};
+		base_url.trim_end_matches('/').to_string()
+	}
+
+	pub fn user_agent(&self) -> String {
+		let user_agent = concat!("aqa_aoc/", env!("CARGO_PKG_VERSION"));
+		match &self.contact {
+			Some(contact) => format!("{} ({})", user_agent, contact),
+			None => user_agent.to_string(),
+		}
+	}
+
+	/// Client with the session, throttled by the state in the workspace.
+	pub fn client(&self) -> anyhow::Result<&AocClient> {
+		if let Some(client) = self.client.get() {
+			return Ok(client);
+		}
+
+		let client = AocClient::new(&self.base_url(), &self.session()?.cookie)
+			.user_agent(&self.user_agent())
+			.throttle(Throttle {
+				path: self.root.join(STATE_DIR).join("last_request"),
+				delay: self
+					.request_delay_ms
+					.map(Duration::from_millis)
+					.unwrap_or(throttle::DEFAULT_DELAY),
+			})
+			.log_requests(true);
+		Ok(self.client.get_or_init(|| client))
+	}
+
+	/// Fails with a clear message when AoC doesn't accept the session anymore, instead of letting
+	/// the next request fail in a confusing way. Returns the name of the user.
+	pub fn check_session(&self) -> anyhow::Result<&str> {
+		if let Some(user) = self.user.get() {
+			return Ok(user);
+		}
+
+		match self.client()?.whoami(self.year)? {
+			Some(user) => Ok(self.user.get_or_init(|| user)),
+			None => Err(AocError::BadSession {
+				origin: Some(self.session()?.source.to_string()),
+			}
+			.into()),
+		}
+	}
+
+	/// Adds where the session came from to a [AocError::BadSession] of a request.
+	pub fn with_session_origin(&self, err: anyhow::Error) -> anyhow::Error {
+		match (err.downcast_ref::<AocError>(), self.session.get()) {
+			(Some(AocError::BadSession { origin: None }), Some(session)) => AocError::BadSession {
+				origin: Some(session.source.to_string()),
+			}
+			.into(),
+			_ => err,
+		}
+	}
+}
+
+/// Sets a top-level `key` of aoc_cfg.toml, keeping the rest of the file as it is.
+pub fn set_value(root: &Path, key: &str, value: &str) -> anyhow::Result<()> {
+	let path = root.join(CFG_FILE);
+	let mut cfg = std::fs::read_to_string(&path)
+		.context("failed to read aoc_cfg.toml")?
+		.parse::<toml_edit::DocumentMut>()
+		.context("failed to parse aoc_cfg.toml")?;
+	cfg[key] = toml_edit::value(value);
+	std::fs::write(&path, cfg.to_string()).context("failed to write aoc_cfg.toml")
+}
+
+/// Layout and year for commands that don't need the full aoc_cfg.toml. Without a year from the
+/// command line or the config, the current year is used.
+pub fn load_workspace_cfg(root: &Path, year: Option<u32>) -> anyhow::Result<(Layout, u32)> {
+	let cfg: WorkspaceCfg = match std::fs::read(root.join(CFG_FILE)) {
+		Ok(cfg) => toml::from_slice(&cfg).context("failed to parse aoc_cfg.toml")?,
+		Err(err) if err.kind() == std::io::ErrorKind::NotFound => WorkspaceCfg::default(),
+		Err(err) => return Err(err).context("failed to read aoc_cfg.toml"),
+	};
+	let year = year
+		.or(cfg.year)
+		.unwrap_or_else(|| date::current_year() as u32);
+	Ok((cfg.layout, year))
+}
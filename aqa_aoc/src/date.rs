@@ -0,0 +1,111 @@
+//! Calendar arithmetic for the few dates the tool cares about, all in UTC.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Puzzles unlock at midnight in UTC-5.
+const UNLOCK_OFFSET_SECS: u64 = 5 * 60 * 60;
+
+/// Converts days since 1970-01-01 to a (year, month, day) date. Howard Hinnant's algorithm.
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+	let z = days + 719468;
+	let era = z.div_euclid(146097);
+	let doe = z.rem_euclid(146097);
+	let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+	let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+	let mp = (5 * doy + 2) / 153;
+	let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+	let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+	let year = yoe + era * 400 + i64::from(month <= 2);
+	(year, month, day)
+}
+
+/// Inverse of [civil_from_days].
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+	let year = year - i64::from(month <= 2);
+	let era = year.div_euclid(400);
+	let yoe = year.rem_euclid(400);
+	let mp = i64::from((month + 9) % 12);
+	let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+	let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+	era * 146097 + doe - 719468
+}
+
+/// Unix time at which the puzzle of `day` in December of `year` unlocks.
+pub fn unlock_time(year: u32, day: u32) -> u64 {
+	days_from_civil(i64::from(year), 12, day) as u64 * SECS_PER_DAY + UNLOCK_OFFSET_SECS
+}
+
+/// Formats a duration as `HH:MM:SS`, with as many hours as needed.
+pub fn format_hms(secs: u64) -> String {
+	format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+pub fn now_unix() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|duration| duration.as_secs())
+		.unwrap_or_default()
+}
+
+pub fn current_year() -> i64 {
+	civil_from_days((now_unix() / SECS_PER_DAY) as i64).0
+}
+
+/// Formats a unix time as `YYYY-MM-DD HH:MM:SS UTC`.
+pub fn format_unix(secs: u64) -> String {
+	let (year, month, day) = civil_from_days((secs / SECS_PER_DAY) as i64);
+	let secs_of_day = secs % SECS_PER_DAY;
+	format!(
+		"{}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+		year,
+		month,
+		day,
+		secs_of_day / 3600,
+		secs_of_day / 60 % 60,
+		secs_of_day % 60
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn civil_from_days() {
+		assert_eq!(super::civil_from_days(0), (1970, 1, 1));
+		assert_eq!(super::civil_from_days(19327), (2022, 12, 1));
+		assert_eq!(super::civil_from_days(11016), (2000, 2, 29));
+		assert_eq!(super::civil_from_days(-1), (1969, 12, 31));
+	}
+
+	#[test]
+	fn days_from_civil() {
+		for days in [-1, 0, 11016, 19327, 20000] {
+			let (year, month, day) = super::civil_from_days(days);
+			assert_eq!(super::days_from_civil(year, month, day), days);
+		}
+	}
+
+	#[test]
+	fn unlock_time() {
+		assert_eq!(
+			super::format_unix(super::unlock_time(2022, 1)),
+			"2022-12-01 05:00:00 UTC"
+		);
+		assert_eq!(
+			super::format_unix(super::unlock_time(2015, 25)),
+			"2015-12-25 05:00:00 UTC"
+		);
+	}
+
+	#[test]
+	fn format_hms() {
+		assert_eq!(super::format_hms(59), "00:00:59");
+		assert_eq!(super::format_hms(26 * 3600 + 61), "26:01:01");
+	}
+
+	#[test]
+	fn format_unix() {
+		assert_eq!(super::format_unix(1669870805), "2022-12-01 05:00:05 UTC");
+	}
+}
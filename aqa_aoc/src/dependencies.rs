@@ -0,0 +1,276 @@
+//! Dependencies of new day crates, from the `[dependencies]` table of aoc_cfg.toml. It is written
+//! like the one of a Cargo.toml, except that paths are relative to the workspace root.
+
+use crate::layout::DayCrate;
+use crate::manifest::CargoTomlParserError;
+use anyhow::bail;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use toml_edit::{Array, DocumentMut, InlineTable, Item, Table, Value};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum Dependency {
+	Version(String),
+	Detailed(Detailed),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Detailed {
+	pub version: Option<String>,
+	/// Relative to the workspace root, e.g. a crate shared by all days
+	pub path: Option<PathBuf>,
+	/// Inherit the dependency from `[workspace.dependencies]`. With a version or path it is added
+	/// there when missing.
+	#[serde(default)]
+	pub workspace: bool,
+	#[serde(default)]
+	pub features: Vec<String>,
+	pub default_features: Option<bool>,
+	pub package: Option<String>,
+}
+
+pub type Dependencies = BTreeMap<String, Dependency>;
+
+/// Crate shared by all days, in the workspace root. The default template builds on it.
+pub const COMMON_CRATE: &str = "aoc_common";
+
+/// Used when aoc_cfg.toml has no `[dependencies]`. [COMMON_CRATE] is only one of them when the
+/// workspace in `root` has it.
+pub fn defaults(root: &Path) -> Dependencies {
+	let mut dependencies = [
+		("anyhow", "1.0"),
+		("itertools", "0.10"),
+		("regex", "1.4"),
+		("nom", "7.0.0"),
+	]
+	.into_iter()
+	.map(|(name, version)| (name.to_string(), Dependency::Version(version.to_string())))
+	.collect::<Dependencies>();
+	if root.join(COMMON_CRATE).join("Cargo.toml").is_file() {
+		dependencies.insert(
+			COMMON_CRATE.to_string(),
+			Dependency::Detailed(Detailed {
+				path: Some(COMMON_CRATE.into()),
+				..Detailed::default()
+			}),
+		);
+	}
+	dependencies
+}
+
+/// The entry of the dependency in the Cargo.toml of `day_crate`.
+pub fn crate_entry(dependency: &Dependency, day_crate: &DayCrate) -> Item {
+	let detailed = match dependency {
+		Dependency::Version(version) => return toml_edit::value(version),
+		Dependency::Detailed(detailed) => detailed,
+	};
+
+	let mut entry = InlineTable::new();
+	if detailed.workspace {
+		// The rest comes from the workspace, a member may only add features
+		entry.insert("workspace", true.into());
+	} else {
+		if let Some(version) = &detailed.version {
+			entry.insert("version", version.into());
+		}
+		if let Some(path) = &detailed.path {
+			entry.insert("path", relative_to(path, day_crate).into());
+		}
+		if let Some(default_features) = detailed.default_features {
+			entry.insert("default-features", default_features.into());
+		}
+		if let Some(package) = &detailed.package {
+			entry.insert("package", package.into());
+		}
+	}
+	if !detailed.features.is_empty() {
+		entry.insert(
+			"features",
+			Value::Array(detailed.features.iter().collect::<Array>()),
+		);
+	}
+
+	Item::Value(Value::InlineTable(entry))
+}
+
+/// Adds the `workspace = true` dependencies that `[workspace.dependencies]` of the workspace
+/// manifest doesn't have yet. Returns their names.
+pub fn add_to_workspace(
+	cargo_workspace: &mut DocumentMut,
+	dependencies: &Dependencies,
+) -> anyhow::Result<Vec<String>> {
+	let inherited = dependencies
+		.iter()
+		.filter_map(|(name, dependency)| match dependency {
+			Dependency::Detailed(detailed) if detailed.workspace => Some((name, detailed)),
+			_ => None,
+		})
+		.collect::<Vec<_>>();
+	if inherited.is_empty() {
+		return Ok(Vec::new());
+	}
+
+	let workspace = cargo_workspace
+		.get_mut("workspace")
+		.and_then(Item::as_table_mut)
+		.ok_or(CargoTomlParserError::FailedToGetWorkspace)?;
+	let workspace_dependencies = workspace
+		.entry("dependencies")
+		.or_insert_with(|| Item::Table(Table::new()))
+		.as_table_like_mut()
+		.ok_or(CargoTomlParserError::FailedToGetDependencies)?;
+
+	let mut added = Vec::new();
+	for (name, detailed) in inherited {
+		if workspace_dependencies.contains_key(name) {
+			continue;
+		}
+
+		let mut entry = InlineTable::new();
+		if let Some(version) = &detailed.version {
+			entry.insert("version", version.into());
+		}
+		if let Some(path) = &detailed.path {
+			entry.insert("path", path.display().to_string().into());
+		}
+		if let Some(default_features) = detailed.default_features {
+			entry.insert("default-features", default_features.into());
+		}
+		if let Some(package) = &detailed.package {
+			entry.insert("package", package.into());
+		}
+		if entry.is_empty() {
+			bail!(
+				"`{}` is inherited from the workspace, but [workspace.dependencies] of Cargo.toml doesn't have it. Add it there, or give a version or path in aoc_cfg.toml",
+				name
+			);
+		}
+
+		let entry = match (entry.len(), &detailed.version) {
+			(1, Some(version)) => toml_edit::value(version),
+			_ => Item::Value(Value::InlineTable(entry)),
+		};
+		workspace_dependencies.insert(name, entry);
+		added.push(name.clone());
+	}
+
+	Ok(added)
+}
+
+/// `path` as seen from the directory of `day_crate`.
+fn relative_to(path: &Path, day_crate: &DayCrate) -> String {
+	if path.is_absolute() {
+		return path.display().to_string();
+	}
+	let depth = day_crate.dir().components().count();
+	format!("{}{}", "../".repeat(depth), path.display())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Dependencies, Dependency, Detailed};
+	use crate::layout::Layout;
+
+	fn parse(toml: &str) -> Dependencies {
+		toml::from_str(toml).unwrap()
+	}
+
+	#[test]
+	fn crate_entries() {
+		let dependencies = parse(
+			r#"
+			anyhow = "1.0"
+			rayon = { version = "1.6", features = ["web_spin_lock"] }
+			aoc_common = { path = "aoc_common" }
+			itertools = { workspace = true, features = ["use_std"] }
+			"#,
+		);
+		assert_eq!(
+			dependencies["aoc_common"],
+			Dependency::Detailed(Detailed {
+				path: Some("aoc_common".into()),
+				..Detailed::default()
+			})
+		);
+
+		let flat = Layout::Flat.day_crate(2022, 1);
+		let entries = dependencies
+			.iter()
+			.map(|(name, dependency)| {
+				format!(
+					"{} = {}",
+					name,
+					super::crate_entry(dependency, &flat).to_string().trim()
+				)
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(
+			entries,
+			vec![
+				"anyhow = \"1.0\"",
+				"aoc_common = { path = \"../aoc_common\" }",
+				"itertools = { workspace = true, features = [\"use_std\"] }",
+				"rayon = { version = \"1.6\", features = [\"web_spin_lock\"] }",
+			]
+		);
+
+		let by_year = Layout::ByYear.day_crate(2022, 1);
+		assert_eq!(
+			super::crate_entry(&dependencies["aoc_common"], &by_year)
+				.to_string()
+				.trim(),
+			"{ path = \"../../aoc_common\" }"
+		);
+
+		assert!(toml::from_str::<Dependencies>("x = { verison = \"1\" }").is_err());
+	}
+
+	#[test]
+	fn defaults() {
+		let root = tempfile::tempdir().unwrap();
+		assert!(!super::defaults(root.path()).contains_key(super::COMMON_CRATE));
+
+		std::fs::create_dir(root.path().join("aoc_common")).unwrap();
+		std::fs::write(
+			root.path().join("aoc_common/Cargo.toml"),
+			"[package]\nname = \"aoc_common\"\n",
+		)
+		.unwrap();
+		assert_eq!(
+			super::defaults(root.path())[super::COMMON_CRATE],
+			Dependency::Detailed(Detailed {
+				path: Some("aoc_common".into()),
+				..Detailed::default()
+			})
+		);
+	}
+
+	#[test]
+	fn add_to_workspace() {
+		let mut cargo_workspace =
+			"[workspace]\nmembers = ['day_1']\n\n[workspace.dependencies]\nitertools = \"0.12\"\n"
+				.parse()
+				.unwrap();
+		let dependencies = parse(
+			r#"
+			itertools = { workspace = true, version = "0.10" }
+			regex = { workspace = true, version = "1.4" }
+			anyhow = "1.0"
+			"#,
+		);
+		assert_eq!(
+			super::add_to_workspace(&mut cargo_workspace, &dependencies).unwrap(),
+			vec!["regex"]
+		);
+		assert_eq!(
+			cargo_workspace.to_string(),
+			"[workspace]\nmembers = ['day_1']\n\n[workspace.dependencies]\nitertools = \"0.12\"\nregex = \"1.4\"\n"
+		);
+
+		let dependencies = parse("nom = { workspace = true }");
+		assert!(super::add_to_workspace(&mut cargo_workspace, &dependencies).is_err());
+	}
+}
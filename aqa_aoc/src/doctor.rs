@@ -0,0 +1,319 @@
+use crate::config::AocCfg;
+use crate::fetch;
+use crate::layout::{self, DayCrate, Layout};
+use crate::manifest;
+use crate::workspace::Workspace;
+use anyhow::{anyhow, Context};
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::Path;
+use toml_edit::DocumentMut;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+	MemberWithoutDirectory(String),
+	DirectoryNotAMember(String),
+	MissingInput(DayCrate),
+	MembersOutOfOrder,
+}
+
+impl Display for Problem {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Problem::MemberWithoutDirectory(member) => {
+				write!(f, "workspace member \"{}\" has no directory", member)
+			}
+			Problem::DirectoryNotAMember(dir) => {
+				write!(f, "crate \"{}\" is not a workspace member", dir)
+			}
+			Problem::MissingInput(day_crate) => {
+				write!(f, "{} has no input.txt", day_crate.member())
+			}
+			Problem::MembersOutOfOrder => write!(f, "day members are not in numeric order"),
+		}
+	}
+}
+
+/// Compares the workspace members of `cargo_workspace` with the crates in `root` and, with the
+/// [Layout::ByYear] layout, in the directory of `year`. Only inputs of `year` are checked. Glob
+/// members like `crates/*` are expanded the way cargo does, and `exclude`d directories are left
+/// alone.
+pub fn diagnose(
+	root: &Path,
+	cargo_workspace: &DocumentMut,
+	layout: Layout,
+	year: u32,
+) -> anyhow::Result<Vec<Problem>> {
+	let members = manifest::workspace_members(cargo_workspace)?
+		.iter()
+		.filter_map(toml_edit::Value::as_str)
+		.collect::<Vec<_>>();
+	let excludes = manifest::workspace_excludes(cargo_workspace);
+	let is_excluded = |dir: &str| {
+		excludes
+			.iter()
+			.any(|exclude| Path::new(dir).starts_with(exclude))
+	};
+
+	let mut problems = Vec::new();
+	let mut member_dirs = Vec::new();
+
+	for member in &members {
+		if is_glob(member) {
+			member_dirs.extend(expand_glob(root, member)?);
+		} else {
+			if !root.join(member).is_dir() {
+				problems.push(Problem::MemberWithoutDirectory(member.to_string()));
+			}
+			member_dirs.push(member.to_string());
+		}
+	}
+	member_dirs.retain(|dir| !is_excluded(dir));
+
+	let mut crate_dirs = list_crate_dirs(root, None)?;
+	let year_dir = layout.year_dir(year);
+	if layout == Layout::ByYear && root.join(&year_dir).is_dir() {
+		crate_dirs.extend(list_crate_dirs(root, year_dir.to_str())?);
+	}
+	crate_dirs.sort_by_key(|dir| (layout::day_key(dir), dir.clone()));
+
+	for dir in &crate_dirs {
+		if !member_dirs.contains(dir) && !is_excluded(dir) {
+			problems.push(Problem::DirectoryNotAMember(dir.clone()));
+		}
+	}
+
+	for dir in &crate_dirs {
+		if let Some(day) = layout.member_day(year, dir) {
+			if !root.join(dir).join("input.txt").exists() {
+				problems.push(Problem::MissingInput(layout.day_crate(year, day)));
+			}
+		}
+	}
+
+	let days = members
+		.iter()
+		.filter_map(|member| layout::day_key(member))
+		.collect::<Vec<_>>();
+	if days.windows(2).any(|pair| pair[0] > pair[1]) {
+		problems.push(Problem::MembersOutOfOrder);
+	}
+
+	Ok(problems)
+}
+
+fn is_glob(member: &str) -> bool {
+	member.contains(['*', '?', '['])
+}
+
+/// The directories matching the glob `member`, relative to `root` like members are.
+fn expand_glob(root: &Path, member: &str) -> anyhow::Result<Vec<String>> {
+	let pattern = root.join(member);
+	let pattern = pattern
+		.to_str()
+		.ok_or_else(|| anyhow!("{} is not valid UTF-8", pattern.display()))?;
+
+	let mut dirs = Vec::new();
+	for path in
+		glob::glob(pattern).with_context(|| format!("invalid workspace member \"{}\"", member))?
+	{
+		let path = path?;
+		if !path.is_dir() {
+			continue;
+		}
+		if let Ok(dir) = path.strip_prefix(root) {
+			let components = dir
+				.components()
+				.map(|component| component.as_os_str().to_string_lossy())
+				.collect::<Vec<_>>();
+			dirs.push(components.join("/"));
+		}
+	}
+	Ok(dirs)
+}
+
+/// Directories with a Cargo.toml in `dir` (or `root` itself), as workspace members.
+fn list_crate_dirs(root: &Path, dir: Option<&str>) -> std::io::Result<Vec<String>> {
+	let path = match dir {
+		Some(dir) => root.join(dir),
+		None => root.to_path_buf(),
+	};
+	Ok(fs::read_dir(path)?
+		.filter_map(Result::ok)
+		.filter(|entry| entry.path().join("Cargo.toml").is_file())
+		.filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+		.map(|name| match dir {
+			Some(dir) => format!("{}/{}", dir, name),
+			None => name,
+		})
+		.collect())
+}
+
+/// Repairs every problem that only needs the workspace manifest. Missing inputs are left to the
+/// caller, since they need a download.
+pub fn fix_manifest(
+	cargo_workspace: &mut DocumentMut,
+	problems: &[Problem],
+) -> anyhow::Result<Vec<Problem>> {
+	let members = manifest::workspace_members_mut(cargo_workspace)?;
+
+	let mut fixed = Vec::new();
+	for problem in problems {
+		let is_fixed = match problem {
+			Problem::MemberWithoutDirectory(member) => {
+				manifest::remove_workspace_member(members, member)
+			}
+			Problem::DirectoryNotAMember(dir) => manifest::add_workspace_member(members, dir),
+			Problem::MembersOutOfOrder => manifest::sort_day_members(members),
+			Problem::MissingInput(_) => false,
+		};
+		if is_fixed {
+			fixed.push(problem.clone());
+		}
+	}
+
+	Ok(fixed)
+}
+
+/// Repairs the `problems` of the workspace: fixes and saves the manifest, registers the day
+/// crates that became members with the runner, and downloads the missing inputs. Returns the
+/// problems that were fixed.
+pub fn fix(workspace: &mut Workspace, problems: &[Problem]) -> anyhow::Result<Vec<Problem>> {
+	let mut fixed = fix_manifest(workspace.manifest_mut(), problems)?;
+	if !fixed.is_empty() {
+		workspace.save()?;
+	}
+
+	for problem in &fixed {
+		if let Problem::DirectoryNotAMember(dir) = problem {
+			if let Some(day) = workspace.layout.member_day(workspace.year, dir) {
+				workspace.register_day(&workspace.day_crate(day))?;
+			}
+		}
+	}
+
+	let missing_inputs = problems
+		.iter()
+		.filter_map(|problem| match problem {
+			Problem::MissingInput(day_crate) => Some(*day_crate),
+			_ => None,
+		})
+		.collect::<Vec<_>>();
+	if !missing_inputs.is_empty() {
+		// Only loaded here, the other problems don't need a config
+		let cfg = AocCfg::load(workspace.root(), Some(workspace.year))?;
+		for day_crate in missing_inputs {
+			fetch::download_input(&cfg, &day_crate, false)?;
+			fixed.push(Problem::MissingInput(day_crate));
+		}
+	}
+
+	Ok(fixed)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Problem;
+	use crate::layout::Layout;
+	use std::fs;
+	use std::path::Path;
+	use toml_edit::DocumentMut;
+
+	fn add_crate(root: &Path, name: &str, with_input: bool) {
+		fs::create_dir_all(root.join(name)).unwrap();
+		fs::write(root.join(name).join("Cargo.toml"), "").unwrap();
+		if with_input {
+			fs::write(root.join(name).join("input.txt"), "").unwrap();
+		}
+	}
+
+	#[test]
+	fn diagnose_and_fix() {
+		let root = tempfile::tempdir().unwrap();
+		add_crate(root.path(), "aqa_aoc", false);
+		add_crate(root.path(), "day_1", true);
+		add_crate(root.path(), "day_2", false);
+		add_crate(root.path(), "day_10", true);
+		fs::create_dir(root.path().join("target")).unwrap();
+
+		let mut cargo_workspace = "[workspace]\nmembers = [\n    'aqa_aoc',\n    'day_10',\n    'day_1',\n    'day_16',\n]\n"
+			.parse::<DocumentMut>()
+			.unwrap();
+
+		let problems = super::diagnose(root.path(), &cargo_workspace, Layout::Flat, 2022).unwrap();
+		assert_eq!(
+			problems,
+			vec![
+				Problem::MemberWithoutDirectory("day_16".to_string()),
+				Problem::DirectoryNotAMember("day_2".to_string()),
+				Problem::MissingInput(Layout::Flat.day_crate(2022, 2)),
+				Problem::MembersOutOfOrder,
+			]
+		);
+
+		let fixed = super::fix_manifest(&mut cargo_workspace, &problems).unwrap();
+		assert_eq!(fixed.len(), 3);
+		assert_eq!(
+			cargo_workspace.to_string(),
+			"[workspace]\nmembers = [\n    'aqa_aoc',\n    'day_1',\n    'day_2',\n    'day_10',\n]\n"
+		);
+
+		assert_eq!(
+			super::diagnose(root.path(), &cargo_workspace, Layout::Flat, 2022).unwrap(),
+			vec![Problem::MissingInput(Layout::Flat.day_crate(2022, 2))]
+		);
+	}
+
+	#[test]
+	fn diagnose_glob_members() {
+		let root = tempfile::tempdir().unwrap();
+		add_crate(root.path(), "aqa_aoc", false);
+		add_crate(root.path(), "day_1", true);
+		add_crate(root.path(), "crates/grid", false);
+		add_crate(root.path(), "scratch", false);
+
+		let mut cargo_workspace =
+			"[workspace]\nmembers = ['aqa_aoc', 'crates/*', 'day_?']\nexclude = ['scratch']\n"
+				.parse::<DocumentMut>()
+				.unwrap();
+
+		let problems = super::diagnose(root.path(), &cargo_workspace, Layout::Flat, 2022).unwrap();
+		assert_eq!(problems, vec![]);
+		assert!(super::fix_manifest(&mut cargo_workspace, &problems)
+			.unwrap()
+			.is_empty());
+
+		let cargo_workspace = "[workspace]\nmembers = ['aqa_aoc', 'crates/*']\n"
+			.parse::<DocumentMut>()
+			.unwrap();
+		assert_eq!(
+			super::diagnose(root.path(), &cargo_workspace, Layout::Flat, 2022).unwrap(),
+			vec![
+				Problem::DirectoryNotAMember("scratch".to_string()),
+				Problem::DirectoryNotAMember("day_1".to_string()),
+			]
+		);
+	}
+
+	#[test]
+	fn diagnose_by_year() {
+		let root = tempfile::tempdir().unwrap();
+		add_crate(root.path(), "aqa_aoc", false);
+		add_crate(root.path(), "year_2021/day_1", false);
+		add_crate(root.path(), "year_2022/day_1", true);
+		add_crate(root.path(), "year_2022/day_2", false);
+
+		let cargo_workspace =
+			"[workspace]\nmembers = ['aqa_aoc', 'year_2021/day_1', 'year_2022/day_1']\n"
+				.parse::<DocumentMut>()
+				.unwrap();
+
+		assert_eq!(
+			super::diagnose(root.path(), &cargo_workspace, Layout::ByYear, 2022).unwrap(),
+			vec![
+				Problem::DirectoryNotAMember("year_2022/day_2".to_string()),
+				Problem::MissingInput(Layout::ByYear.day_crate(2022, 2)),
+			]
+		);
+	}
+}
@@ -0,0 +1,149 @@
+//! Failures of talking to the AoC servers. Each kind exits with its own code, so that scripts can
+//! tell e.g. a puzzle that isn't unlocked yet from an expired session.
+
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum AocError {
+	#[error("{url} is not available, the puzzle is probably not unlocked yet")]
+	NotUnlocked { url: String },
+	#[error(
+		"the session cookie{} is expired or invalid, log in to adventofcode.com and copy the `session` cookie again",
+		from_origin(.origin)
+	)]
+	BadSession {
+		/// Where the cookie came from, if known
+		origin: Option<String>,
+	},
+	#[error("AoC is rate limiting requests{}", wait_for(.wait))]
+	RateLimited { wait: Option<Duration> },
+	#[error("failed to reach {url}")]
+	Network {
+		url: String,
+		#[source]
+		source: ureq::Error,
+	},
+	#[error("unexpected page from AoC: {0}")]
+	UnexpectedHtml(String),
+	#[error("{url} answered with {status_line}")]
+	Http {
+		url: String,
+		status: u16,
+		status_line: String,
+	},
+}
+
+/// Exit codes of the [AocError] kinds, for the help text. Every other error exits with 1.
+pub const EXIT_CODES: &[(u8, &str)] = &[
+	(3, "the puzzle is not unlocked yet"),
+	(4, "the session cookie is expired or invalid"),
+	(5, "rate limited by AoC"),
+	(6, "network or TLS error"),
+	(7, "unexpected page from AoC"),
+	(8, "any other HTTP error"),
+];
+
+impl AocError {
+	pub fn exit_code(&self) -> u8 {
+		match self {
+			AocError::NotUnlocked { .. } => 3,
+			AocError::BadSession { .. } => 4,
+			AocError::RateLimited { .. } => 5,
+			AocError::Network { .. } => 6,
+			AocError::UnexpectedHtml(_) => 7,
+			AocError::Http { .. } => 8,
+		}
+	}
+}
+
+/// Exit code for an error returned from main, looking through the added context.
+pub fn exit_code(err: &anyhow::Error) -> u8 {
+	err.chain()
+		.find_map(|err| err.downcast_ref::<AocError>())
+		.map(AocError::exit_code)
+		.unwrap_or(1)
+}
+
+/// Returns the body of a successful response. AoC answers 404 for puzzles that aren't unlocked,
+/// and 400 or a 500 with a login prompt when it doesn't like the session cookie.
+pub fn read_response(url: &str, response: ureq::Response) -> Result<String, AocError> {
+	if response.synthetic() {
+		// SAFETY: synthetic returns true only when error is Some
+		return Err(AocError::Network {
+			url: url.to_string(),
+			source: response.into_synthetic_error().unwrap(),
+		});
+	}
+
+	let status = response.status();
+	let status_line = response.status_line().to_string();
+	let body = response.into_string().map_err(|err| AocError::Network {
+		url: url.to_string(),
+		source: err.into(),
+	})?;
+
+	match status {
+		200..=299 => Ok(body),
+		404 => Err(AocError::NotUnlocked {
+			url: url.to_string(),
+		}),
+		400 => Err(AocError::BadSession { origin: None }),
+		500 if body.to_lowercase().contains("log in") => Err(AocError::BadSession { origin: None }),
+		429 => Err(AocError::RateLimited { wait: None }),
+		_ => Err(AocError::Http {
+			url: url.to_string(),
+			status,
+			status_line,
+		}),
+	}
+}
+
+fn from_origin(origin: &Option<String>) -> String {
+	origin
+		.as_ref()
+		.map(|origin| format!(" from {}", origin))
+		.unwrap_or_default()
+}
+
+fn wait_for(wait: &Option<Duration>) -> String {
+	match wait {
+		Some(wait) => format!(", wait {}s", wait.as_secs()),
+		None => String::new(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::AocError;
+
+	#[test]
+	fn exit_codes() {
+		let err = anyhow::Error::from(AocError::BadSession { origin: None })
+			.context("failed to download the input");
+		assert_eq!(super::exit_code(&err), 4);
+		assert_eq!(super::exit_code(&anyhow::anyhow!("other")), 1);
+
+		for (code, _) in super::EXIT_CODES {
+			assert!(*code > 1);
+		}
+	}
+
+	#[test]
+	fn messages() {
+		assert_eq!(
+			AocError::BadSession {
+				origin: Some("the AQA_AOC_SESSION variable".to_string())
+			}
+			.to_string(),
+			"the session cookie from the AQA_AOC_SESSION variable is expired or invalid, log in to adventofcode.com and copy the `session` cookie again"
+		);
+		assert_eq!(
+			AocError::RateLimited {
+				wait: Some(std::time::Duration::from_secs(30))
+			}
+			.to_string(),
+			"AoC is rate limiting requests, wait 30s"
+		);
+	}
+}
@@ -0,0 +1,290 @@
+use crate::html::{self, Token};
+use crate::templates::Vars;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::Path;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Examples {
+	/// Contents of every `<pre><code>` block of the part 1 description
+	pub blocks: Vec<String>,
+	/// Highlighted example answers, one per unlocked part
+	pub answers: [Option<String>; 2],
+}
+
+pub fn extract_examples(puzzle_html: &str) -> Examples {
+	let tokens = html::tokenize(puzzle_html);
+	let articles = html::find_elements(&tokens, "article", Some("day-desc"));
+
+	let mut examples = Examples::default();
+
+	if let Some(part_1) = articles.first() {
+		examples.blocks = html::find_elements(part_1, "pre", None)
+			.into_iter()
+			.map(|pre| html::text(pre))
+			.collect();
+	}
+
+	for (answer, article) in examples.answers.iter_mut().zip(articles) {
+		*answer = highlighted_answer(article);
+	}
+
+	examples
+}
+
+/// AoC highlights the example answer as `<code><em>..</em></code>`. The last one in the article
+/// is usually the final answer, while the earlier ones are intermediate results.
+fn highlighted_answer(article: &[Token<'_>]) -> Option<String> {
+	let wrapped_in = |window: &[Token<'_>], outer: &str, inner: &str| {
+		window[0].is_start(outer)
+			&& window[1].is_start(inner)
+			&& window[3].is_end(inner)
+			&& window[4].is_end(outer)
+	};
+
+	article
+		.windows(5)
+		.filter(|window| wrapped_in(window, "code", "em") || wrapped_in(window, "em", "code"))
+		.filter_map(|window| match &window[2] {
+			Token::Text(text) => Some(text.trim().to_string()),
+			_ => None,
+		})
+		.rfind(|answer| !answer.is_empty() && !answer.contains(char::is_whitespace))
+}
+
+/// Picks the block that is the example input. `choice` is 1-based; without it the user is asked
+/// when there is more than one block and stdin is a terminal.
+pub fn choose_example(blocks: &[String], choice: Option<usize>) -> anyhow::Result<Option<&str>> {
+	if let Some(choice) = choice {
+		return blocks
+			.get(choice.wrapping_sub(1))
+			.map(|block| Some(block.as_str()))
+			.ok_or_else(|| {
+				anyhow::anyhow!(
+					"there is no example {}, the puzzle has {} code blocks",
+					choice,
+					blocks.len()
+				)
+			});
+	}
+
+	if blocks.len() <= 1 || !std::io::stdin().is_terminal() {
+		return Ok(blocks.first().map(String::as_str));
+	}
+
+	println!("The puzzle has {} code blocks:", blocks.len());
+	for (idx, block) in blocks.iter().enumerate() {
+		let first_line = block.lines().next().unwrap_or_default();
+		println!(
+			"  [{}] {} ({} lines)",
+			idx + 1,
+			first_line,
+			block.lines().count()
+		);
+	}
+
+	loop {
+		print!("Which one is the example? [1]: ");
+		std::io::stdout().flush()?;
+
+		let mut line = String::new();
+		std::io::stdin().lock().read_line(&mut line)?;
+		let line = line.trim();
+		if line.is_empty() {
+			return Ok(blocks.first().map(String::as_str));
+		}
+		match line.parse::<usize>() {
+			Ok(choice) if (1..=blocks.len()).contains(&choice) => {
+				return Ok(Some(&blocks[choice - 1]))
+			}
+			_ => println!("Pick a number between 1 and {}", blocks.len()),
+		}
+	}
+}
+
+/// Renders the tests module template with the example and the expected answers.
+pub fn render_tests(
+	tests_template: &str,
+	vars: &Vars,
+	example: &str,
+	answers: &[Option<String>; 2],
+) -> String {
+	vars.clone()
+		.with("example", string_literal(example))
+		.with(
+			"part_1_assertion",
+			render_assertion(1, answers[0].as_deref()),
+		)
+		.with(
+			"part_2_assertion",
+			render_assertion(2, answers[1].as_deref()),
+		)
+		.render(tests_template)
+}
+
+fn render_assertion(part: usize, answer: Option<&str>) -> String {
+	match answer {
+		Some(answer) if answer.parse::<i64>().is_ok() => format!(
+			"\t\tassert_eq!(super::part_{}(&super::parse_input(EXAMPLE).unwrap()).unwrap(), {});\n",
+			part, answer
+		),
+		Some(answer) => format!(
+			"\t\tassert_eq!(&super::part_{}(&super::parse_input(EXAMPLE).unwrap()).unwrap(), {});\n",
+			part,
+			string_literal(answer)
+		),
+		None => format!(
+			"\t\tlet _ = super::part_{}(&super::parse_input(EXAMPLE).unwrap()).unwrap();\n\t\ttodo!(\"fill in the expected answer\");\n",
+			part
+		),
+	}
+}
+
+fn string_literal(text: &str) -> String {
+	if text.contains('"') || text.contains('\\') {
+		let mut hashes = String::from("#");
+		while text.contains(&format!("\"{}", hashes)) {
+			hashes.push('#');
+		}
+		format!("r{hashes}\"{text}\"{hashes}")
+	} else {
+		format!("\"{}\"", text)
+	}
+}
+
+/// Appends the tests module to `src_path`, unless there already is one. A part 2 stub generated
+/// before part 2 unlocked gets its expected answer filled in.
+pub fn write_tests(
+	src_path: &Path,
+	tests_template: &str,
+	vars: &Vars,
+	example: &str,
+	answers: &[Option<String>; 2],
+) -> std::io::Result<bool> {
+	let src = std::fs::read_to_string(src_path)?;
+
+	let updated = if !src.contains("mod tests") {
+		let separator = if src.ends_with('\n') { "\n" } else { "\n\n" };
+		let tests = render_tests(tests_template, vars, example, answers);
+		format!("{}{}{}", src, separator, tests)
+	} else {
+		match &answers[1] {
+			Some(_) => src.replace(
+				&render_assertion(2, None),
+				&render_assertion(2, answers[1].as_deref()),
+			),
+			None => src.clone(),
+		}
+	};
+
+	if updated == src {
+		return Ok(false);
+	}
+	std::fs::write(src_path, updated)?;
+	Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::layout::Layout;
+	use crate::templates::{Template, Vars};
+
+	const PUZZLE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 5: Supply Stacks ---</h2>
+<p>For example:</p>
+<pre><code>    [D]
+[N] [C]
+ 1   2
+</code></pre>
+<p>Moving <code><em>1</em></code> crate results in:</p>
+<pre><code>[D]
+[N] [C]
+</code></pre>
+<p>The top crates are <em><code>CMZ</code></em>.</p>
+</article>
+<article class="day-desc"><h2>--- Part Two ---</h2>
+<p>In this example, the top crates are <code><em>MCD</em></code>.</p>
+</article>
+</main>"#;
+
+	#[test]
+	fn extract_examples() {
+		let examples = super::extract_examples(PUZZLE);
+		assert_eq!(
+			examples.blocks,
+			vec!["    [D]\n[N] [C]\n 1   2\n", "[D]\n[N] [C]\n"]
+		);
+		assert_eq!(
+			examples.answers,
+			[Some("CMZ".to_string()), Some("MCD".to_string())]
+		);
+	}
+
+	#[test]
+	fn choose_example() {
+		let blocks = vec!["a".to_string(), "b".to_string()];
+		assert_eq!(super::choose_example(&blocks, Some(2)).unwrap(), Some("b"));
+		assert!(super::choose_example(&blocks, Some(3)).is_err());
+		assert!(super::choose_example(&blocks, Some(0)).is_err());
+	}
+
+	#[test]
+	fn render_tests() {
+		let tests = super::render_tests(
+			Template::default().tests(),
+			&Vars::new(&Layout::Flat.day_crate(2022, 1)),
+			"1\n\"2\"\n",
+			&[Some("24000".to_string()), None],
+		);
+		assert_eq!(
+			tests,
+			r##"#[cfg(test)]
+mod tests {
+	const EXAMPLE: &str = r#"1
+"2"
+"#;
+
+	#[test]
+	fn part_1_example() {
+		assert_eq!(super::part_1(&super::parse_input(EXAMPLE).unwrap()).unwrap(), 24000);
+	}
+
+	#[test]
+	fn part_2_example() {
+		let _ = super::part_2(&super::parse_input(EXAMPLE).unwrap()).unwrap();
+		todo!("fill in the expected answer");
+	}
+}
+"##
+		);
+	}
+
+	#[test]
+	fn write_tests_fills_in_part_2() {
+		let dir = tempfile::tempdir().unwrap();
+		let main_rs = dir.path().join("main.rs");
+		std::fs::write(&main_rs, "fn main() {}\n").unwrap();
+
+		let template = Template::default();
+		let vars = Vars::new(&Layout::Flat.day_crate(2022, 1));
+
+		let answers = [Some("1".to_string()), None];
+		let written =
+			super::write_tests(&main_rs, template.tests(), &vars, "1\n", &answers).unwrap();
+		assert!(written);
+		assert!(std::fs::read_to_string(&main_rs)
+			.unwrap()
+			.contains("todo!(\"fill in the expected answer\")"));
+
+		let answers = [Some("1".to_string()), Some("CMZ".to_string())];
+		assert!(super::write_tests(&main_rs, template.tests(), &vars, "1\n", &answers).unwrap());
+		assert_eq!(
+			std::fs::read_to_string(&main_rs).unwrap(),
+			format!(
+				"fn main() {{}}\n\n{}",
+				super::render_tests(template.tests(), &vars, "1\n", &answers)
+			)
+		);
+
+		assert!(!super::write_tests(&main_rs, template.tests(), &vars, "1\n", &answers).unwrap());
+	}
+}
@@ -0,0 +1,190 @@
+//! What a day crate gets from AoC: its input, and the puzzle description with tests generated from
+//! the example in it.
+
+use crate::cache::{self, Cached};
+use crate::config::AocCfg;
+use crate::date;
+use crate::error::AocError;
+use crate::examples;
+use crate::gitignore;
+use crate::layout::DayCrate;
+use crate::puzzle;
+use crate::templates::{self, Template};
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Download {
+	/// The input was there already and was left alone
+	Kept { input_path: PathBuf, kept: Kept },
+	Saved {
+		input_path: PathBuf,
+		/// Where the input is stored when `input.txt` links to it, see [cache::Storage]
+		stored_at: Option<PathBuf>,
+		/// The inputs were added to .gitignore
+		gitignore_updated: bool,
+	},
+}
+
+/// Why an input isn't downloaded again.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Kept {
+	Fetched {
+		at: u64,
+	},
+	OtherSession {
+		at: u64,
+	},
+	OtherYear {
+		at: u64,
+		year: u32,
+	},
+	Modified {
+		at: u64,
+	},
+	/// Not downloaded by this tool
+	Untracked,
+}
+
+impl Display for Kept {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Kept::Fetched { at } => write!(f, "was downloaded on {}", date::format_unix(*at)),
+			Kept::OtherSession { at } => write!(
+				f,
+				"was downloaded on {} with a different session",
+				date::format_unix(*at)
+			),
+			Kept::OtherYear { at, year } => write!(
+				f,
+				"was downloaded on {} for year {}",
+				date::format_unix(*at),
+				year
+			),
+			Kept::Modified { at } => write!(
+				f,
+				"was downloaded on {} and modified since",
+				date::format_unix(*at)
+			),
+			Kept::Untracked => write!(f, "already exists"),
+		}
+	}
+}
+
+/// Downloads the input unless it's already there. `force` downloads it anyway.
+pub fn download_input(cfg: &AocCfg, day_crate: &DayCrate, force: bool) -> anyhow::Result<Download> {
+	let crate_dir = day_crate.dir();
+	let input_path = cache::input_path(&crate_dir);
+
+	let cached = cache::lookup(&crate_dir)?;
+	if !force && cached != Cached::Missing {
+		let session = cache::session_fingerprint(&cfg.session()?.cookie);
+		let kept = match cached {
+			Cached::Fetched(meta) if meta.session != session => Kept::OtherSession {
+				at: meta.fetched_at,
+			},
+			Cached::Fetched(meta) if meta.year != cfg.year => Kept::OtherYear {
+				at: meta.fetched_at,
+				year: meta.year,
+			},
+			Cached::Fetched(meta) => Kept::Fetched {
+				at: meta.fetched_at,
+			},
+			Cached::Modified(meta) => Kept::Modified {
+				at: meta.fetched_at,
+			},
+			Cached::Untracked | Cached::Missing => Kept::Untracked,
+		};
+		return Ok(Download::Kept { input_path, kept });
+	}
+
+	// No separate session check, AoC refusing the session for the input says as much
+	let input = cfg
+		.client()?
+		.input(day_crate.year, day_crate.day)
+		.map_err(|err| cfg.with_session_origin(err))?;
+
+	let meta = cache::InputMeta {
+		year: day_crate.year,
+		day: day_crate.day,
+		fetched_at: date::now_unix(),
+		sha256: cache::sha256_hex(&input),
+		session: cache::session_fingerprint(&cfg.session()?.cookie),
+	};
+	let root = Path::new(".");
+	let stored_at = cfg.inputs.outside_crate(root, &crate_dir);
+	cache::store(&crate_dir, stored_at.as_deref(), &meta, &input)?;
+
+	let gitignore_updated = match stored_at {
+		Some(_) => gitignore::update(root, &gitignore::input_entries(cfg.layout))?,
+		None => false,
+	};
+	Ok(Download::Saved {
+		input_path,
+		stored_at,
+		gitignore_updated,
+	})
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PuzzleUpdate {
+	/// Where the description was saved, `None` when README.md was up to date
+	pub readme: Option<PathBuf>,
+	pub tests: Tests,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Tests {
+	NoExample,
+	Written(PathBuf),
+	/// The tests of the example are there already
+	Unchanged,
+}
+
+/// Saves the puzzle description as README.md and generates tests from the example. `example` is
+/// the code block with the example, asked for when it isn't given and there are several.
+pub fn update_from_puzzle(
+	cfg: &AocCfg,
+	day_crate: &DayCrate,
+	example: Option<usize>,
+) -> anyhow::Result<PuzzleUpdate> {
+	let client = cfg.client()?;
+	let puzzle = client.puzzle(day_crate.year, day_crate.day)?;
+	let markdown = puzzle::puzzle_to_markdown(&puzzle, client.base_url()).ok_or_else(|| {
+		AocError::UnexpectedHtml("no puzzle description on the puzzle page".to_string())
+	})?;
+
+	let readme_path = day_crate.dir().join("README.md");
+	let readme = puzzle::save_readme(&readme_path, &markdown)?.then_some(readme_path);
+
+	let examples = examples::extract_examples(&puzzle);
+	let Some(example) = examples::choose_example(&examples.blocks, example)? else {
+		return Ok(PuzzleUpdate {
+			readme,
+			tests: Tests::NoExample,
+		});
+	};
+
+	let template = Template::load(cfg.template_dir.as_deref())?;
+	let vars = templates::Vars::new(day_crate);
+
+	// The default template keeps the solution in lib.rs, user templates may only have a main.rs
+	let src_dir = day_crate.dir().join("src");
+	let src_path = match src_dir.join("lib.rs") {
+		lib_rs if lib_rs.exists() => lib_rs,
+		_ => src_dir.join("main.rs"),
+	};
+	let tests = if examples::write_tests(
+		&src_path,
+		template.tests(),
+		&vars,
+		example,
+		&examples.answers,
+	)? {
+		Tests::Written(src_path)
+	} else {
+		Tests::Unchanged
+	};
+
+	Ok(PuzzleUpdate { readme, tests })
+}
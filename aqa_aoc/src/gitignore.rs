@@ -0,0 +1,130 @@
+//! The entries of the workspace .gitignore that keep the inputs out of git, in a block between
+//! marker comments so that they can be updated without touching the rest of the file.
+
+use crate::cache::META_FILE;
+use crate::config::STATE_DIR;
+use crate::layout::Layout;
+use anyhow::Context;
+use std::fs;
+use std::path::Path;
+
+const BEGIN: &str = "# Puzzle inputs, managed by aqa_aoc";
+const END: &str = "# End of puzzle inputs";
+
+/// Entries for inputs stored in the cache: the cache itself, and the links in the day crates with
+/// the metadata of the inputs next to them.
+pub fn input_entries(layout: Layout) -> Vec<String> {
+	let crates = match layout {
+		Layout::Flat => "/day_*",
+		Layout::ByYear => "/year_*/day_*",
+	};
+	vec![
+		format!("/{}/", STATE_DIR),
+		format!("{}/input.txt", crates),
+		format!("{}/{}", crates, META_FILE),
+	]
+}
+
+/// `contents` with the managed block set to `entries`, appended when there's no block yet. Entries
+/// that are in the file already, outside of the block, are left out of it, and so is the block when
+/// that leaves it empty.
+pub fn with_entries(contents: &str, entries: &[String]) -> String {
+	let (before, after) = match managed_block(contents) {
+		Some((begin, end)) => (&contents[..begin], Some(&contents[end..])),
+		None => (contents, None),
+	};
+	let elsewhere = |entry: &String| {
+		before
+			.lines()
+			.chain(after.unwrap_or_default().lines())
+			.any(|line| line.trim() == entry)
+	};
+
+	let mut block = String::new();
+	for entry in entries.iter().filter(|entry| !elsewhere(entry)) {
+		block.push_str(entry);
+		block.push('\n');
+	}
+	if !block.is_empty() {
+		block = format!("{}\n{}{}\n", BEGIN, block, END);
+	}
+
+	if let Some(after) = after {
+		return format!("{}{}{}", before, block, after);
+	}
+	if block.is_empty() {
+		return contents.to_string();
+	}
+	match contents {
+		"" => block,
+		contents if contents.ends_with('\n') => format!("{}\n{}", contents, block),
+		contents => format!("{}\n\n{}", contents, block),
+	}
+}
+
+/// Start and end of the managed block in `contents`, the end is past its last line.
+fn managed_block(contents: &str) -> Option<(usize, usize)> {
+	let begin = contents.find(&format!("{}\n", BEGIN))?;
+	let end = contents[begin..].find(&format!("{}\n", END))?;
+	Some((begin, begin + end + END.len() + 1))
+}
+
+/// Writes the managed block of the .gitignore in `root`. `false` when it was up to date.
+pub fn update(root: &Path, entries: &[String]) -> anyhow::Result<bool> {
+	let path = root.join(".gitignore");
+	let contents = match fs::read_to_string(&path) {
+		Ok(contents) => contents,
+		Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+		Err(err) => return Err(err).with_context(|| format!("failed to read {}", path.display())),
+	};
+
+	let updated = with_entries(&contents, entries);
+	if updated == contents {
+		return Ok(false);
+	}
+	fs::write(&path, updated).with_context(|| format!("failed to write {}", path.display()))?;
+	Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::layout::Layout;
+
+	#[test]
+	fn with_entries() {
+		let entries = super::input_entries(Layout::Flat);
+		let block = "# Puzzle inputs, managed by aqa_aoc\n/.aqa_aoc/\n/day_*/input.txt\n/day_*/input.meta.toml\n# End of puzzle inputs\n";
+
+		assert_eq!(super::with_entries("", &entries), block);
+		assert_eq!(
+			super::with_entries("target/", &entries),
+			format!("target/\n\n{}", block)
+		);
+
+		let gitignore = format!("target/\n\n{}Cargo.lock\n", block);
+		assert_eq!(super::with_entries(&gitignore, &entries), gitignore);
+		assert_eq!(
+			super::with_entries(&gitignore, &super::input_entries(Layout::ByYear)),
+			"target/\n\n# Puzzle inputs, managed by aqa_aoc\n/.aqa_aoc/\n/year_*/day_*/input.txt\n/year_*/day_*/input.meta.toml\n# End of puzzle inputs\nCargo.lock\n"
+		);
+	}
+
+	#[test]
+	fn with_entries_already_ignored() {
+		let entries = super::input_entries(Layout::Flat);
+		assert_eq!(
+			super::with_entries("target/\n/.aqa_aoc/\n", &entries),
+			"target/\n/.aqa_aoc/\n\n# Puzzle inputs, managed by aqa_aoc\n/day_*/input.txt\n/day_*/input.meta.toml\n# End of puzzle inputs\n"
+		);
+
+		// An entry added by hand after the block is dropped from it
+		let gitignore = "# Puzzle inputs, managed by aqa_aoc\n/.aqa_aoc/\n/day_*/input.txt\n/day_*/input.meta.toml\n# End of puzzle inputs\n/.aqa_aoc/\n";
+		assert_eq!(
+			super::with_entries(gitignore, &entries),
+			"# Puzzle inputs, managed by aqa_aoc\n/day_*/input.txt\n/day_*/input.meta.toml\n# End of puzzle inputs\n/.aqa_aoc/\n"
+		);
+
+		let everything = "/.aqa_aoc/\n/day_*/input.txt\n/day_*/input.meta.toml\n";
+		assert_eq!(super::with_entries(everything, &entries), everything);
+	}
+}
@@ -0,0 +1,287 @@
+//! Just enough HTML handling for the pages AoC serves. Not a general purpose parser.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token<'a> {
+	Start {
+		name: &'a str,
+		attrs: Vec<(&'a str, String)>,
+	},
+	End {
+		name: &'a str,
+	},
+	Text(String),
+}
+
+impl<'a> Token<'a> {
+	pub fn attr(&self, attr_name: &str) -> Option<&str> {
+		match self {
+			Token::Start { attrs, .. } => attrs
+				.iter()
+				.find(|(name, _)| name.eq_ignore_ascii_case(attr_name))
+				.map(|(_, value)| value.as_str()),
+			_ => None,
+		}
+	}
+
+	pub fn has_class(&self, class: &str) -> bool {
+		self.attr("class")
+			.map(|classes| classes.split_whitespace().any(|c| c == class))
+			.unwrap_or_default()
+	}
+
+	pub fn is_start(&self, tag: &str) -> bool {
+		matches!(self, Token::Start { name, .. } if name.eq_ignore_ascii_case(tag))
+	}
+
+	pub fn is_end(&self, tag: &str) -> bool {
+		matches!(self, Token::End { name } if name.eq_ignore_ascii_case(tag))
+	}
+}
+
+pub fn tokenize(html: &str) -> Vec<Token<'_>> {
+	let mut tokens = Vec::new();
+	let mut rest = html;
+
+	while !rest.is_empty() {
+		let tag_start = match rest.find('<') {
+			Some(idx) => idx,
+			None => {
+				tokens.push(Token::Text(decode_entities(rest)));
+				break;
+			}
+		};
+
+		if tag_start > 0 {
+			tokens.push(Token::Text(decode_entities(&rest[..tag_start])));
+		}
+		rest = &rest[tag_start..];
+
+		if let Some(comment) = rest.strip_prefix("<!--") {
+			rest = comment
+				.find("-->")
+				.map(|idx| &comment[idx + 3..])
+				.unwrap_or_default();
+			continue;
+		}
+
+		let tag_end = match rest.find('>') {
+			Some(idx) => idx,
+			None => {
+				tokens.push(Token::Text(decode_entities(rest)));
+				break;
+			}
+		};
+		let tag = &rest[1..tag_end];
+		rest = &rest[tag_end + 1..];
+
+		if tag.starts_with('!') || tag.starts_with('?') {
+			continue;
+		}
+
+		if let Some(name) = tag.strip_prefix('/') {
+			tokens.push(Token::End { name: name.trim() });
+			continue;
+		}
+
+		let self_closing = tag.ends_with('/');
+		let tag = tag.trim_end_matches('/');
+		let (name, attrs) = match tag.find(char::is_whitespace) {
+			Some(idx) => (&tag[..idx], parse_attrs(&tag[idx..])),
+			None => (tag, Vec::new()),
+		};
+
+		tokens.push(Token::Start { name, attrs });
+
+		if self_closing {
+			tokens.push(Token::End { name });
+		} else if name.eq_ignore_ascii_case("script") || name.eq_ignore_ascii_case("style") {
+			let closing = format!("</{}", name);
+			let end = rest.find(&closing).unwrap_or(rest.len());
+			rest = &rest[end..];
+		}
+	}
+
+	tokens
+}
+
+fn parse_attrs(mut attrs: &str) -> Vec<(&str, String)> {
+	let mut parsed = Vec::new();
+
+	loop {
+		attrs = attrs.trim_start();
+		if attrs.is_empty() {
+			break;
+		}
+
+		let name_end = attrs
+			.find(|c: char| c == '=' || c.is_whitespace())
+			.unwrap_or(attrs.len());
+		let name = &attrs[..name_end];
+		attrs = attrs[name_end..].trim_start();
+
+		let value = match attrs.strip_prefix('=') {
+			Some(value) => {
+				let value = value.trim_start();
+				let (raw, rest) = match value.chars().next() {
+					Some(quote @ ('"' | '\'')) => {
+						let value = &value[1..];
+						let end = value.find(quote).unwrap_or(value.len());
+						(&value[..end], value.get(end + 1..).unwrap_or_default())
+					}
+					_ => {
+						let end = value.find(char::is_whitespace).unwrap_or(value.len());
+						(&value[..end], &value[end..])
+					}
+				};
+				attrs = rest;
+				decode_entities(raw)
+			}
+			None => String::new(),
+		};
+
+		parsed.push((name, value));
+	}
+
+	parsed
+}
+
+pub fn decode_entities(text: &str) -> String {
+	let mut decoded = String::with_capacity(text.len());
+	let mut rest = text;
+
+	while let Some(idx) = rest.find('&') {
+		decoded.push_str(&rest[..idx]);
+		rest = &rest[idx..];
+
+		let entity_end = rest.find(';').filter(|end| *end <= 10);
+		let entity = entity_end.map(|end| &rest[1..end]);
+		let c = match entity {
+			Some("amp") => Some('&'),
+			Some("lt") => Some('<'),
+			Some("gt") => Some('>'),
+			Some("quot") => Some('"'),
+			Some("apos") => Some('\''),
+			Some("nbsp") => Some('\u{a0}'),
+			Some(entity) => entity
+				.strip_prefix("#x")
+				.or_else(|| entity.strip_prefix("#X"))
+				.and_then(|hex| u32::from_str_radix(hex, 16).ok())
+				.or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+				.and_then(char::from_u32),
+			None => None,
+		};
+
+		match (c, entity_end) {
+			(Some(c), Some(end)) => {
+				decoded.push(c);
+				rest = &rest[end + 1..];
+			}
+			_ => {
+				decoded.push('&');
+				rest = &rest[1..];
+			}
+		}
+	}
+	decoded.push_str(rest);
+
+	decoded
+}
+
+/// Returns the contents (without the enclosing tags) of every `tag` element that has `class`.
+pub fn find_elements<'t, 'a>(
+	tokens: &'t [Token<'a>],
+	tag: &str,
+	class: Option<&str>,
+) -> Vec<&'t [Token<'a>]> {
+	let mut elements = Vec::new();
+
+	let mut idx = 0;
+	while idx < tokens.len() {
+		let token = &tokens[idx];
+		idx += 1;
+
+		if !token.is_start(tag) || !class.map(|class| token.has_class(class)).unwrap_or(true) {
+			continue;
+		}
+
+		let start = idx;
+		let mut depth = 1;
+		while idx < tokens.len() {
+			if tokens[idx].is_start(tag) {
+				depth += 1;
+			} else if tokens[idx].is_end(tag) {
+				depth -= 1;
+				if depth == 0 {
+					break;
+				}
+			}
+			idx += 1;
+		}
+		elements.push(&tokens[start..idx]);
+	}
+
+	elements
+}
+
+/// Concatenated text of the tokens, with all markup dropped.
+pub fn text(tokens: &[Token<'_>]) -> String {
+	tokens
+		.iter()
+		.filter_map(|token| match token {
+			Token::Text(text) => Some(text.as_str()),
+			_ => None,
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Token;
+
+	#[test]
+	fn tokenize() {
+		let tokens = super::tokenize(
+			"<!DOCTYPE html><!-- c --><p class=\"a b\" id=x>1 &lt; 2<br/></p><script>if (a < b) {}</script>",
+		);
+		assert_eq!(
+			tokens,
+			vec![
+				Token::Start {
+					name: "p",
+					attrs: vec![("class", "a b".to_string()), ("id", "x".to_string())],
+				},
+				Token::Text("1 < 2".to_string()),
+				Token::Start {
+					name: "br",
+					attrs: vec![]
+				},
+				Token::End { name: "br" },
+				Token::End { name: "p" },
+				Token::Start {
+					name: "script",
+					attrs: vec![]
+				},
+				Token::End { name: "script" },
+			]
+		);
+	}
+
+	#[test]
+	fn decode_entities() {
+		assert_eq!(
+			super::decode_entities("&amp;&#39;&#x41;&quot; & x;&bogus;"),
+			"&'A\" & x;&bogus;"
+		);
+	}
+
+	#[test]
+	fn find_elements() {
+		let tokens = super::tokenize(
+			"<article class=\"day-desc\"><p>one</p></article><article><p>skip</p></article><article class=\"day-desc\"><p>two</p></article>",
+		);
+		let articles = super::find_elements(&tokens, "article", Some("day-desc"));
+		assert_eq!(articles.len(), 2);
+		assert_eq!(super::text(articles[0]), "one");
+		assert_eq!(super::text(articles[1]), "two");
+	}
+}
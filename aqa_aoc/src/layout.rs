@@ -0,0 +1,138 @@
+//! Where the crate of each day lives in the workspace.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Layout {
+	/// `day_N` crates in the workspace root, all of them for the same year
+	#[default]
+	Flat,
+	/// `year_YYYY/day_N` directories with `year_YYYY_day_N` crates, any number of years side by side
+	ByYear,
+}
+
+impl Layout {
+	pub fn day_crate(self, year: u32, day: u32) -> DayCrate {
+		DayCrate {
+			layout: self,
+			year,
+			day,
+		}
+	}
+
+	/// Directory with the `day_N` directories of `year`.
+	pub fn year_dir(self, year: u32) -> PathBuf {
+		match self {
+			Layout::Flat => PathBuf::from("."),
+			Layout::ByYear => PathBuf::from(format!("year_{}", year)),
+		}
+	}
+
+	/// Day of a workspace member, if it is a day crate of `year`.
+	pub fn member_day(self, year: u32, member: &str) -> Option<u32> {
+		match (self, day_key(member)?) {
+			(Layout::Flat, (None, day)) => Some(day),
+			(Layout::ByYear, (Some(member_year), day)) if member_year == year => Some(day),
+			_ => None,
+		}
+	}
+
+	/// Days of `year` that have a directory, in numeric order.
+	pub fn days(self, root: &Path, year: u32) -> std::io::Result<Vec<u32>> {
+		let year_dir = root.join(self.year_dir(year));
+		if !year_dir.is_dir() {
+			return Ok(Vec::new());
+		}
+
+		let mut days = fs::read_dir(year_dir)?
+			.filter_map(Result::ok)
+			.filter(|dir| dir.path().is_dir())
+			.filter_map(|dir| {
+				dir.file_name()
+					.to_str()
+					.and_then(|name| name.strip_prefix("day_"))
+					.and_then(|day| day.parse::<u32>().ok())
+			})
+			.collect::<Vec<_>>();
+		days.sort_unstable();
+		Ok(days)
+	}
+}
+
+/// Sort key of a `day_N` or `year_YYYY/day_N` member, `None` for other members.
+pub fn day_key(member: &str) -> Option<(Option<u32>, u32)> {
+	let (year, day) = match member.split_once('/') {
+		Some((year, day)) => (Some(year.strip_prefix("year_")?.parse().ok()?), day),
+		None => (None, member),
+	};
+	Some((year, day.strip_prefix("day_")?.parse().ok()?))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayCrate {
+	pub layout: Layout,
+	pub year: u32,
+	pub day: u32,
+}
+
+impl DayCrate {
+	/// Path of the crate relative to the workspace root, as written in the workspace members.
+	pub fn member(&self) -> String {
+		match self.layout {
+			Layout::Flat => format!("day_{}", self.day),
+			Layout::ByYear => format!("year_{}/day_{}", self.year, self.day),
+		}
+	}
+
+	/// Package name, unique across the workspace.
+	pub fn name(&self) -> String {
+		match self.layout {
+			Layout::Flat => format!("day_{}", self.day),
+			Layout::ByYear => format!("year_{}_day_{}", self.year, self.day),
+		}
+	}
+
+	pub fn dir(&self) -> PathBuf {
+		PathBuf::from(self.member())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Layout;
+
+	#[test]
+	fn day_crates() {
+		let flat = Layout::Flat.day_crate(2022, 3);
+		assert_eq!(flat.member(), "day_3");
+		assert_eq!(flat.name(), "day_3");
+
+		let by_year = Layout::ByYear.day_crate(2022, 3);
+		assert_eq!(by_year.member(), "year_2022/day_3");
+		assert_eq!(by_year.name(), "year_2022_day_3");
+	}
+
+	#[test]
+	fn member_day() {
+		assert_eq!(Layout::Flat.member_day(2022, "day_12"), Some(12));
+		assert_eq!(Layout::Flat.member_day(2022, "year_2022/day_12"), None);
+		assert_eq!(Layout::Flat.member_day(2022, "aqa_aoc"), None);
+		assert_eq!(
+			Layout::ByYear.member_day(2022, "year_2022/day_12"),
+			Some(12)
+		);
+		assert_eq!(Layout::ByYear.member_day(2022, "year_2021/day_12"), None);
+		assert_eq!(Layout::ByYear.member_day(2022, "day_12"), None);
+	}
+
+	#[test]
+	fn day_key() {
+		assert_eq!(super::day_key("day_7"), Some((None, 7)));
+		assert_eq!(super::day_key("year_2015/day_25"), Some((Some(2015), 25)));
+		assert_eq!(super::day_key("year_2015/aqa_aoc"), None);
+		assert_eq!(super::day_key("aqa_aoc"), None);
+	}
+}
@@ -0,0 +1,363 @@
+//! Private leaderboards, from the JSON AoC serves for them.
+
+use crate::config::{AocCfg, STATE_DIR};
+use crate::date;
+use crate::error::AocError;
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+/// AoC asks not to request a leaderboard more often than this.
+pub const CACHE_FOR: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+	Table,
+	Json,
+	Csv,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+	pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+	pub id: u64,
+	/// `None` for anonymous users
+	pub name: Option<String>,
+	pub stars: u32,
+	pub local_score: u64,
+	/// Day -> part -> star
+	#[serde(default)]
+	pub completion_day_level: BTreeMap<String, BTreeMap<String, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+	pub get_star_ts: u64,
+}
+
+/// A member with the stars of every day, ready for display or export.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Standing {
+	pub rank: usize,
+	pub id: u64,
+	pub name: String,
+	pub local_score: u64,
+	pub stars: u32,
+	pub days: Vec<DayStars>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct DayStars {
+	pub day: u32,
+	/// Unix times the stars were gotten at
+	pub part_1: Option<u64>,
+	pub part_2: Option<u64>,
+	/// Seconds between the two stars
+	pub delta: Option<u64>,
+}
+
+/// Returns the contents of `path` if it was written less than [CACHE_FOR] ago, otherwise stores
+/// what `fetch` returns there. The second value is the age of the cached copy.
+pub fn load_cached(
+	path: &Path,
+	fetch: impl FnOnce() -> anyhow::Result<String>,
+) -> anyhow::Result<(String, Option<Duration>)> {
+	let age = fs::metadata(path)
+		.and_then(|metadata| metadata.modified())
+		.ok()
+		.and_then(|modified| SystemTime::now().duration_since(modified).ok());
+	if let Some(age) = age.filter(|age| *age < CACHE_FOR) {
+		let cached = fs::read_to_string(path)
+			.with_context(|| format!("failed to read {}", path.display()))?;
+		return Ok((cached, Some(age)));
+	}
+
+	let json = fetch()?;
+	// Only cache what parses, a login page shouldn't block the next try for 15 minutes
+	parse(&json)?;
+	if let Some(dir) = path.parent() {
+		fs::create_dir_all(dir)?;
+	}
+	fs::write(path, &json).with_context(|| format!("failed to write {}", path.display()))?;
+	Ok((json, None))
+}
+
+/// Standings of the private leaderboard `id` in the year of `cfg`, downloaded at most every
+/// [CACHE_FOR]. The second value is the age of the cached copy they come from.
+pub fn fetch_standings(
+	cfg: &AocCfg,
+	id: &str,
+) -> anyhow::Result<(Vec<Standing>, Option<Duration>)> {
+	let cache_path = Path::new(STATE_DIR)
+		.join("leaderboard")
+		.join(format!("{}_{}.json", cfg.year, id));
+	let (json, age) = load_cached(&cache_path, || cfg.client()?.leaderboard(cfg.year, id))?;
+	Ok((standings(&parse(&json)?), age))
+}
+
+pub fn parse(json: &str) -> anyhow::Result<Leaderboard> {
+	if json.trim_start().starts_with('<') {
+		return Err(AocError::UnexpectedHtml(
+			"got a page instead of the leaderboard JSON, check the leaderboard id and the session cookie"
+				.to_string(),
+		)
+		.into());
+	}
+	serde_json::from_str(json).context("failed to parse the leaderboard JSON")
+}
+
+/// Members ordered by local score, then by stars.
+pub fn standings(leaderboard: &Leaderboard) -> Vec<Standing> {
+	let mut members = leaderboard.members.values().collect::<Vec<_>>();
+	members.sort_by(|a, b| {
+		b.local_score
+			.cmp(&a.local_score)
+			.then(b.stars.cmp(&a.stars))
+			.then(a.id.cmp(&b.id))
+	});
+
+	members
+		.into_iter()
+		.enumerate()
+		.map(|(idx, member)| {
+			let mut days = member
+				.completion_day_level
+				.iter()
+				.filter_map(|(day, parts)| {
+					let star = |part: &str| parts.get(part).map(|star| star.get_star_ts);
+					let (part_1, part_2) = (star("1"), star("2"));
+					Some(DayStars {
+						day: day.parse().ok()?,
+						part_1,
+						part_2,
+						delta: part_1.zip(part_2).map(|(p1, p2)| p2.saturating_sub(p1)),
+					})
+				})
+				.collect::<Vec<_>>();
+			days.sort_by_key(|day| day.day);
+
+			Standing {
+				rank: idx + 1,
+				id: member.id,
+				name: member
+					.name
+					.clone()
+					.unwrap_or_else(|| format!("(anonymous user #{})", member.id)),
+				local_score: member.local_score,
+				stars: member.stars,
+				days,
+			}
+		})
+		.collect()
+}
+
+/// The standings, followed by the star times of every day (or just `only_day`), counted from the
+/// unlock of the puzzle.
+pub fn render_table(standings: &[Standing], year: u32, only_day: Option<u32>) -> String {
+	let name_width = standings
+		.iter()
+		.map(|standing| standing.name.chars().count())
+		.max()
+		.unwrap_or_default()
+		.max("Name".len());
+
+	let mut table = format!("{:>3}  {:>5}  {:>5}  Name\n", "#", "Score", "Stars");
+	for standing in standings {
+		table += &format!(
+			"{:>3}  {:>5}  {:>5}  {}\n",
+			standing.rank, standing.local_score, standing.stars, standing.name
+		);
+	}
+
+	let mut days = standings
+		.iter()
+		.flat_map(|standing| standing.days.iter().map(|day| day.day))
+		.filter(|day| only_day.map(|only_day| only_day == *day).unwrap_or(true))
+		.collect::<Vec<_>>();
+	days.sort_unstable();
+	days.dedup();
+
+	for day in days {
+		let unlock_time = date::unlock_time(year, day);
+		let since_unlock = |ts: Option<u64>| {
+			ts.map(|ts| date::format_hms(ts.saturating_sub(unlock_time)))
+				.unwrap_or_default()
+		};
+
+		let mut rows = standings
+			.iter()
+			.filter_map(|standing| {
+				let stars = standing.days.iter().find(|stars| stars.day == day)?;
+				Some((standing, stars))
+			})
+			.collect::<Vec<_>>();
+		// Finishing order: both stars first, by the time of the last one
+		rows.sort_by_key(|(_, stars)| (stars.part_2.is_none(), stars.part_2, stars.part_1));
+
+		table += &format!(
+			"\nDay {:<width$}  {:>10}  {:>10}  {:>10}\n",
+			day,
+			"Part 1",
+			"Part 2",
+			"Delta",
+			width = name_width - 2
+		);
+		for (standing, stars) in rows {
+			let row = format!(
+				"  {:<width$}  {:>10}  {:>10}  {:>10}",
+				standing.name,
+				since_unlock(stars.part_1),
+				since_unlock(stars.part_2),
+				stars.delta.map(date::format_hms).unwrap_or_default(),
+				width = name_width
+			);
+			table += row.trim_end();
+			table.push('\n');
+		}
+	}
+
+	table
+}
+
+/// One row per member and day with stars, members without any stars get a row without a day.
+pub fn to_csv(standings: &[Standing]) -> String {
+	let mut csv = "rank,id,name,local_score,stars,day,part_1,part_2,delta\n".to_string();
+	let optional = |value: Option<u64>| value.map(|value| value.to_string()).unwrap_or_default();
+
+	for standing in standings {
+		let member = format!(
+			"{},{},{},{},{}",
+			standing.rank,
+			standing.id,
+			csv_field(&standing.name),
+			standing.local_score,
+			standing.stars
+		);
+		if standing.days.is_empty() {
+			csv += &format!("{},,,,\n", member);
+		}
+		for day in &standing.days {
+			csv += &format!(
+				"{},{},{},{},{}\n",
+				member,
+				day.day,
+				optional(day.part_1),
+				optional(day.part_2),
+				optional(day.delta)
+			);
+		}
+	}
+
+	csv
+}
+
+pub fn to_json(standings: &[Standing]) -> String {
+	// SAFETY: the standings are plain structs, serializing them can't fail
+	serde_json::to_string_pretty(standings).unwrap()
+}
+
+fn csv_field(field: &str) -> String {
+	if field.contains([',', '"', '\n']) {
+		format!("\"{}\"", field.replace('"', "\"\""))
+	} else {
+		field.to_string()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::DayStars;
+
+	const LEADERBOARD: &str = r#"{
+		"owner_id": 1, "event": "2022",
+		"members": {
+			"1": {"id": 1, "name": "Alice", "stars": 3, "local_score": 6, "global_score": 0, "last_star_ts": 1669957500,
+				"completion_day_level": {
+					"1": {"1": {"get_star_ts": 1669870990, "star_index": 1}, "2": {"get_star_ts": 1669871300, "star_index": 2}},
+					"2": {"1": {"get_star_ts": 1669957500, "star_index": 3}}
+				}},
+			"2": {"id": 2, "name": null, "stars": 2, "local_score": 4, "global_score": 0, "last_star_ts": 1669871200,
+				"completion_day_level": {
+					"1": {"1": {"get_star_ts": 1669870900, "star_index": 0}, "2": {"get_star_ts": 1669871200, "star_index": 2}}
+				}},
+			"3": {"id": 3, "name": "Bob, Jr.", "stars": 0, "local_score": 0, "global_score": 0, "last_star_ts": 0,
+				"completion_day_level": {}}
+		}
+	}"#;
+
+	#[test]
+	fn standings() {
+		let standings = super::standings(&super::parse(LEADERBOARD).unwrap());
+
+		let names = standings
+			.iter()
+			.map(|standing| (standing.rank, standing.name.as_str()))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			names,
+			vec![(1, "Alice"), (2, "(anonymous user #2)"), (3, "Bob, Jr.")]
+		);
+		assert_eq!(
+			standings[0].days,
+			vec![
+				DayStars {
+					day: 1,
+					part_1: Some(1669870990),
+					part_2: Some(1669871300),
+					delta: Some(310),
+				},
+				DayStars {
+					day: 2,
+					part_1: Some(1669957500),
+					part_2: None,
+					delta: None,
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn table() {
+		let standings = super::standings(&super::parse(LEADERBOARD).unwrap());
+		let table = super::render_table(&standings, 2022, Some(1));
+
+		assert_eq!(
+			table,
+			"  #  Score  Stars  Name
+  1      6      3  Alice
+  2      4      2  (anonymous user #2)
+  3      0      0  Bob, Jr.
+
+Day 1                      Part 1      Part 2       Delta
+  (anonymous user #2)    00:01:40    00:06:40    00:05:00
+  Alice                  00:03:10    00:08:20    00:05:10
+"
+		);
+	}
+
+	#[test]
+	fn csv() {
+		let standings = super::standings(&super::parse(LEADERBOARD).unwrap());
+		assert_eq!(
+			super::to_csv(&standings),
+			"rank,id,name,local_score,stars,day,part_1,part_2,delta
+1,1,Alice,6,3,1,1669870990,1669871300,310
+1,1,Alice,6,3,2,1669957500,,
+2,2,(anonymous user #2),4,2,1,1669870900,1669871200,300
+3,3,\"Bob, Jr.\",0,0,,,,
+"
+		);
+	}
+
+	#[test]
+	fn login_page_is_not_a_leaderboard() {
+		assert!(super::parse("<!DOCTYPE html><html></html>").is_err());
+	}
+}
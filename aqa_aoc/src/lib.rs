@@ -0,0 +1,35 @@
+//! Advent of Code helpers for a cargo workspace with a crate per day: an [AocClient] that
+//! downloads inputs and puzzles, submits answers and reads leaderboards, and a [Workspace] that
+//! keeps track of the day crates. The `aqa_aoc` binary is a front-end to both.
+
+pub mod answers;
+pub mod archive;
+pub mod cache;
+pub mod calendar;
+pub mod client;
+pub mod config;
+pub mod date;
+pub mod dependencies;
+pub mod doctor;
+pub mod error;
+pub mod examples;
+pub mod fetch;
+pub mod gitignore;
+mod html;
+pub mod layout;
+pub mod leaderboard;
+pub mod manifest;
+pub mod puzzle;
+pub mod run;
+pub mod session;
+pub mod submit;
+pub mod templates;
+pub mod throttle;
+pub mod unlock;
+pub mod watch;
+pub mod workspace;
+
+pub use client::AocClient;
+pub use config::AocCfg;
+pub use error::AocError;
+pub use workspace::Workspace;
@@ -1,285 +1,558 @@
-use anyhow::Context;
-use serde::Deserialize;
-use std::fs::{self, File};
-use std::io::Write;
-use std::path::Path;
-use std::process::Command;
-use thiserror::Error;
-use ureq::Cookie;
-
-static AOC_ADDR: &str = "https://adventofcode.com";
-
-#[derive(Deserialize)]
-struct AocCfg {
-	year: u32,
-	session_cookie: String,
+use aqa_aoc::config::{self, AocCfg};
+use aqa_aoc::layout::DayCrate;
+use aqa_aoc::workspace::{self, Day, Workspace};
+use aqa_aoc::{
+	answers, archive, cache, calendar, doctor, error, fetch, leaderboard, run, submit, templates,
+	unlock, watch,
+};
+use cli::{CliApp, CliCommand};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+mod cli;
+
+fn main() -> ExitCode {
+	match run_cli() {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(err) => {
+			eprintln!("Error: {:?}", err);
+			ExitCode::from(error::exit_code(&err))
+		}
+	}
 }
 
-#[derive(Debug, Error)]
-pub enum CargoTomlParserError {
-	#[error("failed to get \"workspace\" section")]
-	FailedToGetWorkspace,
-	#[error("failed to get \"members\" array")]
-	FailedToGetMembers,
-	#[error("failed to get \"dependencies\" section")]
-	FailedToGetDependencies,
-}
+fn run_cli() -> anyhow::Result<()> {
+	let cli = CliApp::from_args()?;
 
-#[derive(Debug)]
-struct CliApp {
-	day: Option<u32>,
-}
+	if let CliCommand::Help { command } = &cli.command {
+		println!("{}", cli::help(command.as_deref()));
+		return Ok(());
+	}
 
-impl CliApp {
-	fn from_args() -> anyhow::Result<CliApp> {
-		let mut day = None;
+	match cli.command {
+		CliCommand::Status { tests, offline } => return status(cli.year, tests, offline),
+		CliCommand::Doctor { fix } => return doctor(cli.year, fix),
+		_ => (),
+	}
+
+	let cfg = AocCfg::load(Path::new("."), cli.year)?;
 
-		for arg in std::env::args().skip(1) {
-			if let Some(arg) = arg.strip_prefix("--day=") {
-				day = Some(arg.parse::<u32>()?);
+	match cli.command {
+		CliCommand::NewAndFetch {
+			example,
+			force,
+			wait: false,
+		} => {
+			let day = new_day(&cfg, cli.day)?;
+			fetch(&cfg, day.get_day(), example, force)
+		}
+		CliCommand::NewAndFetch {
+			example,
+			force,
+			wait: true,
+		} => {
+			// Decide on the day before waiting, the crate is only created once the puzzle is out
+			let day = open_workspace(&cfg)?.pick_day(cli.day)?.get_day();
+			unlock::wait_for_unlock(cfg.year, day);
+
+			new_day(&cfg, Some(day))?;
+			unlock::retry(|| fetch(&cfg, day, example, force))
+		}
+		CliCommand::New => {
+			if let Day::CreatedBefore(day) = new_day(&cfg, cli.day)? {
+				println!("{} already exists", cfg.day_crate(day).member());
 			}
+			Ok(())
+		}
+		CliCommand::Fetch {
+			example,
+			force,
+			wait,
+		} => {
+			let day = existing_day(&cfg, cli.day)?;
+			if wait {
+				unlock::wait_for_unlock(cfg.year, day);
+				unlock::retry(|| fetch(&cfg, day, example, force))
+			} else {
+				fetch(&cfg, day, example, force)
+			}
+		}
+		CliCommand::Run { part, release } => {
+			let day = existing_day(&cfg, cli.day)?;
+			run(&cfg.day_crate(day), part, release)
+		}
+		CliCommand::Submit {
+			part,
+			answer,
+			force,
+		} => {
+			// SAFETY: the parser doesn't accept submit without --day
+			submit(&cfg, cli.day.unwrap(), part, &answer, force)
+		}
+		CliCommand::Watch { release } => {
+			let day = existing_day(&cfg, cli.day)?;
+			watch(&cfg.day_crate(day), release)
+		}
+		CliCommand::Whoami => whoami(&cfg),
+		CliCommand::MigrateInputs => migrate_inputs(&cfg),
+		CliCommand::Archive { to, remove } => archive(&cfg, to.as_deref(), remove),
+		CliCommand::Restore { bundle } => restore(&cfg, Path::new(&bundle)),
+		CliCommand::Leaderboard { id, format } => leaderboard(&cfg, &id, cli.day, format),
+		CliCommand::Help { .. } | CliCommand::Status { .. } | CliCommand::Doctor { .. } => {
+			unreachable!()
 		}
-
-		Ok(CliApp { day })
 	}
 }
 
-fn main() -> anyhow::Result<()> {
-	let cli = CliApp::from_args()?;
+fn open_workspace(cfg: &AocCfg) -> anyhow::Result<Workspace> {
+	Workspace::open(Path::new("."), cfg.layout, cfg.year)
+}
 
-	let cfg: AocCfg =
-		toml::from_slice(&std::fs::read("aoc_cfg.toml").context("failed to read aoc_cfg.toml")?)
-			.context("failed to parse aoc_cfg.toml")?;
+/// Picks the day to work with and creates its crate if it doesn't exist.
+fn new_day(cfg: &AocCfg, specific_day: Option<u32>) -> anyhow::Result<Day> {
+	let mut workspace = open_workspace(cfg)?;
 
-	let mut cargo_workspace: toml::Value =
-		toml::from_slice(&std::fs::read("Cargo.toml").context("failed to read Cargo.toml")?)
-			.context("failed to parse Cargo.toml")?;
-
-	let day = match cli.day {
-		Some(specific_day) => work_with_specific_day(&mut cargo_workspace, specific_day)?,
-		None => get_day_we_are_working_with(&mut cargo_workspace)?,
-	};
+	let day = workspace.pick_day(specific_day)?;
 
 	println!("Working with day: {}", day.get_day());
 
-	let day_crate_name = day.get_crate_name();
-
 	if let Day::NewDay(day) = day {
-		println!("Adding \"day_{}\" to workspace members", day);
-
-		let mut cargo_workspace_file = File::create("Cargo.toml")?;
-		let cargo_workspace_str = toml::to_string_pretty(&cargo_workspace)?;
-		cargo_workspace_file.write_all(cargo_workspace_str.as_bytes())?;
+		let day_crate = cfg.day_crate(day);
+		println!("Adding \"{}\" to workspace members", day_crate.member());
+		println!("Creating new crate (\"{}\")", day_crate.name());
 
-		create_crate_for_new_day(&day_crate_name)?;
+		let template = templates::Template::load(cfg.template_dir.as_deref())?;
+		for name in workspace.add_day(day, &template, &cfg.dependencies())? {
+			println!("Added \"{}\" to [workspace.dependencies]", name);
+		}
 	}
 
-	std::env::set_current_dir(&day_crate_name)?;
-
-	println!("Downloading day {} input...", day.get_day());
+	Ok(day)
+}
 
-	let day_1_input = get_input(cfg.year, day.get_day(), &cfg.session_cookie)?;
+/// Like [new_day], but fails instead of creating the crate.
+fn existing_day(cfg: &AocCfg, specific_day: Option<u32>) -> anyhow::Result<u32> {
+	let day = open_workspace(cfg)?.pick_day(specific_day)?;
+
+	match day {
+		Day::CreatedBefore(day) => Ok(day),
+		Day::NewDay(day) => Err(anyhow::anyhow!(
+			"{} doesn't exist, create it with `aqa_aoc new --day {} --year {}`",
+			cfg.day_crate(day).member(),
+			day,
+			cfg.year
+		)),
+	}
+}
 
-	let mut input_file = File::create("input.txt")?;
-	input_file.write_all(day_1_input.as_bytes())?;
+fn fetch(cfg: &AocCfg, day: u32, example: Option<usize>, force: bool) -> anyhow::Result<()> {
+	download_input(cfg, day, force)?;
 
-	println!("Input saved to {}/input.txt", day_crate_name);
+	println!("Downloading day {} puzzle description...", day);
+	update_from_puzzle(cfg, &cfg.day_crate(day), example);
 
 	Ok(())
 }
 
-#[derive(Copy, Clone)]
-enum Day {
-	NewDay(u32),
-	CreatedBefore(u32),
+/// Downloads the input unless it's already there. `force` downloads it anyway.
+fn download_input(cfg: &AocCfg, day: u32, force: bool) -> anyhow::Result<()> {
+	match fetch::download_input(cfg, &cfg.day_crate(day), force)? {
+		fetch::Download::Kept { input_path, kept } => println!(
+			"{} {}, not downloading it again. Use --force to download it anyway",
+			input_path.display(),
+			kept
+		),
+		fetch::Download::Saved {
+			input_path,
+			stored_at: Some(stored_at),
+			gitignore_updated,
+		} => {
+			println!(
+				"Input saved to {}, linked from {}",
+				stored_at.display(),
+				input_path.display()
+			);
+			if gitignore_updated {
+				println!("Added the inputs to .gitignore");
+			}
+		}
+		fetch::Download::Saved { input_path, .. } => {
+			println!("Input saved to {}", input_path.display())
+		}
+	}
+	Ok(())
 }
 
-impl Day {
-	fn get_day(&self) -> u32 {
-		match self {
-			Day::NewDay(day) | Day::CreatedBefore(day) => *day,
+/// Saves the puzzle description and the example tests. Failing to is only reported, the input is
+/// what matters.
+fn update_from_puzzle(cfg: &AocCfg, day_crate: &DayCrate, example: Option<usize>) {
+	let update = match fetch::update_from_puzzle(cfg, day_crate, example) {
+		Ok(update) => update,
+		Err(err) => {
+			eprintln!("Failed to process the puzzle description: {:#}", err);
+			return;
 		}
+	};
+
+	if let Some(readme) = update.readme {
+		println!("Puzzle description saved to {}", readme.display());
 	}
+	match update.tests {
+		fetch::Tests::NoExample => println!("No example found in the puzzle description"),
+		fetch::Tests::Written(path) => println!("Example tests written to {}", path.display()),
+		fetch::Tests::Unchanged => (),
+	}
+}
 
-	fn get_crate_name(&self) -> String {
-		format!("day_{}", self.get_day())
+/// Runs the solution and checks what it prints against answers.toml. Fails when an answer is
+/// known to be wrong.
+fn run(day_crate: &DayCrate, only_part: Option<u32>, release: bool) -> anyhow::Result<()> {
+	let checked = run::run_and_check(day_crate, only_part, release, print_part)?;
+	match checked.wrong.as_slice() {
+		[] => Ok(()),
+		[part] => Err(anyhow::anyhow!("part {} doesn't match answers.toml", part)),
+		_ => Err(anyhow::anyhow!("both parts don't match answers.toml")),
 	}
 }
 
-fn get_day_we_are_working_with(cargo_workspace: &mut toml::Value) -> anyhow::Result<Day> {
-	let workspace = cargo_workspace
-		.get_mut("workspace")
-		.ok_or(CargoTomlParserError::FailedToGetWorkspace)?;
-	let members = workspace
-		.get_mut("members")
-		.ok_or(CargoTomlParserError::FailedToGetMembers)?
-		.as_array_mut()
-		.ok_or(CargoTomlParserError::FailedToGetMembers)?;
-
-	let mut last_day_without_input = None;
-	for dir in fs::read_dir(".")?
-		.filter_map(Result::ok)
-		.filter(|v| v.path().is_dir())
-	{
-		let day = dir
-			.path()
-			.file_name()
-			.and_then(|name| name.to_str())
-			.and_then(|name| name.strip_prefix("day_"))
-			.and_then(|day| day.parse::<u32>().ok());
-
-		let day = match day {
-			Some(v) => v,
-			None => continue,
-		};
+fn print_part(result: &run::PartResult, check: &run::Check) {
+	let verdict = match check {
+		run::Check::Unknown => String::new(),
+		run::Check::Correct => paint("correct", GREEN),
+		run::Check::Wrong { expected } => paint(&format!("wrong, the answer is {}", expected), RED),
+		run::Check::KnownWrong(refusal) => paint(&format!("wrong, {}", refusal), RED),
+	};
+	let time = result
+		.elapsed
+		.map(|elapsed| format!("  ({})", run::format_duration(elapsed)))
+		.unwrap_or_default();
+	if result.answer.contains('\n') {
+		println!(
+			"Part {}:{}  {}\n{}",
+			result.part, time, verdict, result.answer
+		);
+	} else {
+		println!(
+			"Part {}: {}{}  {}",
+			result.part, result.answer, time, verdict
+		);
+	}
+}
 
-		if !dir.path().join("input.txt").exists() {
-			last_day_without_input = Some(day);
-			break;
+/// Runs the tests, then the solution if they pass, every time the sources or the input of the
+/// day change. Runs until interrupted.
+fn watch(day_crate: &DayCrate, release: bool) -> anyhow::Result<()> {
+	let dir = day_crate.dir();
+	let mut watcher = watch::Watcher::new(vec![dir.join("src"), cache::input_path(&dir)]);
+
+	let mut previous = None;
+	loop {
+		println!("Running the tests of {}", day_crate.name());
+		match run::test_and_run(day_crate, release, print_part) {
+			Ok(Some(checked)) => {
+				if let Some(previous) = &previous {
+					println!(
+						"{}",
+						watch::diff_answers(previous, &checked.answers).join(", ")
+					);
+				}
+				previous = Some(checked.answers);
+			}
+			Ok(None) => println!("{}", paint("Tests failed, not running the solution", RED)),
+			Err(err) => eprintln!("Error: {:#}", err),
 		}
-	}
 
-	match last_day_without_input {
-		Some(v) => Ok(Day::CreatedBefore(v)),
-		None => {
-			let day = members
+		println!("Watching {} for changes, Ctrl+C to stop", dir.display());
+		let changed = watcher.wait();
+		println!(
+			"\nChanged: {}",
+			changed
 				.iter()
-				.filter_map(toml::Value::as_str)
-				.filter_map(|str| str.strip_prefix("day_"))
-				.filter_map(|day| day.parse::<u32>().ok())
-				.max()
-				.unwrap_or(0) + 1;
-
-			members.push(toml::Value::String(format!("day_{}", day)));
-			Ok(Day::NewDay(day))
-		}
+				.map(|path| path.display().to_string())
+				.collect::<Vec<_>>()
+				.join(", ")
+		);
 	}
 }
 
-fn work_with_specific_day(
-	cargo_workspace: &mut toml::Value,
-	specific_day: u32,
-) -> anyhow::Result<Day> {
-	let workspace = cargo_workspace
-		.get_mut("workspace")
-		.ok_or(CargoTomlParserError::FailedToGetWorkspace)?;
-	let members = workspace
-		.get_mut("members")
-		.ok_or(CargoTomlParserError::FailedToGetMembers)?
-		.as_array_mut()
-		.ok_or(CargoTomlParserError::FailedToGetMembers)?;
-
-	let mut day_project = None;
-	for dir in fs::read_dir(".")?
-		.filter_map(Result::ok)
-		.filter(|v| v.path().is_dir())
-	{
-		let day = dir
-			.path()
-			.file_name()
-			.and_then(|name| name.to_str())
-			.and_then(|name| name.strip_prefix("day_"))
-			.and_then(|day| day.parse::<u32>().ok());
-
-		match day {
-			Some(day) if day == specific_day => {
-				day_project = Some(day);
-				break;
-			}
-			_ => (),
-		};
-	}
+const GREEN: &str = "32";
+const RED: &str = "31";
 
-	match day_project {
-		Some(v) => Ok(Day::CreatedBefore(v)),
-		None => {
-			members.push(toml::Value::String(format!("day_{}", specific_day)));
-			Ok(Day::NewDay(specific_day))
-		}
+/// Colors `text` when printing to a terminal, unless NO_COLOR is set.
+fn paint(text: &str, color: &str) -> String {
+	if std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none() {
+		format!("\x1b[{}m{}\x1b[0m", color, text)
+	} else {
+		text.to_string()
 	}
 }
 
-fn create_crate_for_new_day(new_crate_name: &str) -> anyhow::Result<()> {
-	println!("Creating new crate (\"{}\")", new_crate_name);
+fn migrate_inputs(cfg: &AocCfg) -> anyhow::Result<()> {
+	let migration = cache::migrate(&open_workspace(cfg)?, cfg)?;
 
-	let cargo_new_status = Command::new("cargo")
-		.args(["new", new_crate_name])
-		.status()?;
-	if !cargo_new_status.success() {
-		return Err(anyhow::anyhow!(
-			"cargo new failed with code {}",
-			cargo_new_status.code().unwrap_or_default()
-		));
+	for (input, stored_at) in &migration.moved {
+		println!("Moved {} to {}", input.display(), stored_at.display());
+	}
+	if migration.gitignore_updated {
+		println!("Added the inputs to .gitignore");
+	}
+	if migration.untracked {
+		println!("Removed the inputs from the git index, commit to stop tracking them");
+	}
+	if migration.cfg_updated {
+		println!("Set `inputs = \"cache\"` in aoc_cfg.toml");
 	}
 
-	add_useful_deps(new_crate_name)?;
+	if migration.moved.is_empty() {
+		println!("No inputs to move");
+	} else {
+		println!(
+			"Moved {} inputs. They are still in the git history, rewrite it before publishing the repository if that matters",
+			migration.moved.len()
+		);
+	}
 
 	Ok(())
 }
 
-fn add_useful_deps(new_crate_name: &str) -> anyhow::Result<()> {
-	let cargo_toml_path = Path::new(new_crate_name).join("Cargo.toml");
-	let mut cargo_toml: toml::Value = toml::from_slice(
-		&std::fs::read(&cargo_toml_path)
-			.with_context(|| format!("failed to read {}", cargo_toml_path.display()))?,
-	)
-	.with_context(|| format!("failed to parse {}", cargo_toml_path.display()))?;
-
-	let dependencies = cargo_toml
-		.get_mut("dependencies")
-		.ok_or(CargoTomlParserError::FailedToGetDependencies)?
-		.as_table_mut()
-		.ok_or(CargoTomlParserError::FailedToGetDependencies)?;
-
-	dependencies.insert("anyhow".to_string(), toml::Value::String("1.0".to_string()));
-	dependencies.insert(
-		"itertools".to_string(),
-		toml::Value::String("0.10".to_string()),
+fn archive(cfg: &AocCfg, to: Option<&str>, remove: bool) -> anyhow::Result<()> {
+	let bundle = match to {
+		Some(to) => PathBuf::from(to),
+		None => archive::default_bundle(cfg.year),
+	};
+
+	let archived = archive::archive(
+		&mut open_workspace(cfg)?,
+		cfg.inputs,
+		&bundle,
+		remove,
+		|day_crate| println!("Running {}", day_crate.name()),
+	)?;
+	for (day_crate, err) in &archived.failed_runs {
+		eprintln!(
+			"Failed to run {}, archived it without timings: {:#}",
+			day_crate.name(),
+			err
+		);
+	}
+	println!(
+		"Archived {} day crates to {}",
+		archived.days.len(),
+		bundle.display()
 	);
-	dependencies.insert("regex".to_string(), toml::Value::String("1.4".to_string()));
-	dependencies.insert("nom".to_string(), toml::Value::String("7.0.0".to_string()));
+	if remove {
+		println!("Removed them from the workspace");
+	}
+
+	Ok(())
+}
 
-	let mut cargo_toml_file = File::create(&cargo_toml_path)?;
-	let cargo_toml_str = toml::to_string_pretty(&cargo_toml)?;
-	cargo_toml_file.write_all(cargo_toml_str.as_bytes())?;
+fn restore(cfg: &AocCfg, bundle: &Path) -> anyhow::Result<()> {
+	let restored = archive::restore(Path::new("."), cfg.inputs, bundle)?;
 
-	println!("[dependencies] updated");
+	for member in &restored.existing {
+		println!("{} already exists, not restoring it", member);
+	}
+	for member in &restored.restored {
+		println!("Restored {}", member);
+	}
+	for member in &restored.mismatched_inputs {
+		eprintln!(
+			"The input of {} doesn't match the hash in the archive",
+			member
+		);
+	}
+	println!(
+		"Restored {} of {} day crates of {}",
+		restored.restored.len(),
+		restored.restored.len() + restored.existing.len(),
+		restored.year
+	);
 
 	Ok(())
 }
 
-fn get_input(year: u32, day: u32, cookie: &str) -> anyhow::Result<String> {
-	let cookie = Cookie::build("session", cookie)
-		.domain("adventofcode.com")
-		.path("/")
-		.secure(true)
-		.finish()
-		.into_owned();
-
-	let agent = ureq::agent();
-	agent.set_cookie(cookie);
-
-	let req_adr = format!(
-		"{addr}/{year}/day/{day}/input",
-		addr = AOC_ADDR,
-		year = year,
-		day = day
+fn whoami(cfg: &AocCfg) -> anyhow::Result<()> {
+	let user = cfg.check_session()?;
+	println!(
+		"Logged in as {}, session cookie from {}",
+		user,
+		cfg.session()?.source
 	);
-	println!("Request addr: {}", req_adr);
-	let mut request = agent.get(&req_adr);
-	let response = request.call();
+	Ok(())
+}
 
-	if response.synthetic() {
-		// SAFETY: synthetic returns true only when error is Some
-		return Err(response.into_synthetic_error().unwrap().into());
+fn leaderboard(
+	cfg: &AocCfg,
+	id: &str,
+	day: Option<u32>,
+	format: leaderboard::Format,
+) -> anyhow::Result<()> {
+	let (standings, age) = leaderboard::fetch_standings(cfg, id)?;
+	if let Some(age) = age {
+		// On stderr, to keep the output parseable
+		eprintln!(
+			"Leaderboard downloaded {} min ago, AoC asks not to fetch it more than every {} min",
+			age.as_secs() / 60,
+			leaderboard::CACHE_FOR.as_secs() / 60
+		);
 	}
 
-	if response.error() {
+	match format {
+		leaderboard::Format::Table => {
+			print!("{}", leaderboard::render_table(&standings, cfg.year, day))
+		}
+		leaderboard::Format::Json => println!("{}", leaderboard::to_json(&standings)),
+		leaderboard::Format::Csv => print!("{}", leaderboard::to_csv(&standings)),
+	}
+	Ok(())
+}
+
+fn submit(cfg: &AocCfg, day: u32, part: u32, answer: &str, force: bool) -> anyhow::Result<()> {
+	let answer = answer.trim();
+	println!("Submitting day {} part {} answer: {}", day, part, answer);
+
+	let (outcome, forced_past, recorded) = match submit::submit(cfg, day, part, answer, force)? {
+		submit::Submission::Refused(refusal) => {
+			return Err(anyhow::anyhow!(
+				"not submitting {}, {}. Use --force to submit it anyway",
+				answer,
+				refusal
+			))
+		}
+		submit::Submission::Sent {
+			outcome,
+			forced_past,
+			recorded,
+		} => (outcome, forced_past, recorded),
+	};
+
+	if let Some(refusal) = forced_past {
+		println!("Submitted anyway, {}", refusal);
+	}
+	println!("{}", outcome);
+
+	let crate_dir = cfg.day_crate(day).dir();
+	if answers::Verdict::from_outcome(outcome).is_some() && !recorded {
+		eprintln!(
+			"{} doesn't exist, the answer isn't recorded in {}",
+			crate_dir.display(),
+			answers::ANSWERS_FILE
+		);
+	}
+
+	if outcome == submit::Outcome::Correct && part == 1 {
+		println!("Downloading day {} part 2 description...", day);
+		update_from_puzzle(cfg, &cfg.day_crate(day), None);
+	}
+
+	Ok(())
+}
+
+fn doctor(year: Option<u32>, fix: bool) -> anyhow::Result<()> {
+	let (layout, year) = config::load_workspace_cfg(Path::new("."), year)?;
+	let mut workspace = Workspace::open(Path::new("."), layout, year)?;
+
+	let problems = doctor::diagnose(workspace.root(), workspace.manifest(), layout, year)?;
+	if problems.is_empty() {
+		println!("No problems found");
+		return Ok(());
+	}
+
+	for problem in &problems {
+		println!("{}", problem);
+	}
+
+	if !fix {
 		return Err(anyhow::anyhow!(
-			"Failed to fetch input: {}",
-			response.status_line()
+			"found {} problem(s), run `aqa_aoc doctor --fix` to repair them",
+			problems.len()
 		));
 	}
 
-	response.into_string().map_err(Into::into)
+	for problem in doctor::fix(&mut workspace, &problems)? {
+		println!("Fixed: {}", problem);
+	}
+
+	Ok(())
+}
+
+fn status(year: Option<u32>, run_tests: bool, offline: bool) -> anyhow::Result<()> {
+	let (layout, year) = config::load_workspace_cfg(Path::new("."), year)?;
+	let days = Workspace::open(Path::new("."), layout, year)?.days()?;
+
+	let stars = if offline {
+		None
+	} else {
+		match AocCfg::load(Path::new("."), Some(year))
+			.and_then(|cfg| calendar::user_stars(&cfg, year))
+		{
+			Ok(stars) => Some(stars),
+			Err(err) => {
+				eprintln!("Stars are unknown: {:#}", err);
+				None
+			}
+		}
+	};
+
+	let width = days
+		.iter()
+		.map(|day| day.day_crate.member().len())
+		.max()
+		.unwrap_or_default()
+		.max("Day".len());
+	let yes_no = |value: bool| if value { "yes" } else { "no" };
+
+	println!(
+		"{:<width$}  Crate  Member  Input  {:<14}  Answers  Stars",
+		"Day",
+		"Tests",
+		width = width
+	);
+	for day in &days {
+		let tests = if run_tests && day.has_crate && day.is_member {
+			match workspace::run_tests(&day.day_crate)? {
+				Some(run) if run.failed == 0 => format!("{} passed", run.passed),
+				Some(run) => format!("{} of {} failed", run.failed, run.passed + run.failed),
+				None => "build failed".to_string(),
+			}
+		} else {
+			day.tests.to_string()
+		};
+		let answers = day
+			.answers
+			.iter()
+			.enumerate()
+			.map(|(idx, answer)| match answer {
+				Some(_) => (idx + 1).to_string(),
+				None => "-".to_string(),
+			})
+			.collect::<Vec<_>>()
+			.join(" ");
+		let stars = match &stars {
+			Some(stars) => "*".repeat(
+				stars
+					.get(&day.day_crate.day)
+					.copied()
+					.unwrap_or_default()
+					.into(),
+			),
+			None => "?".to_string(),
+		};
+
+		println!(
+			"{:<width$}  {:<5}  {:<6}  {:<5}  {:<14}  {:<7}  {}",
+			day.day_crate.member(),
+			yes_no(day.has_crate),
+			yes_no(day.is_member),
+			yes_no(day.has_input),
+			tests,
+			answers,
+			stars,
+			width = width,
+		);
+	}
+
+	Ok(())
 }
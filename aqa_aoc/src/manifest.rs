@@ -0,0 +1,286 @@
+//! Format-preserving edits of Cargo.toml files. Everything that isn't edited stays byte-identical.
+
+use crate::layout::day_key;
+use anyhow::Context;
+use std::path::Path;
+use thiserror::Error;
+use toml_edit::{Array, DocumentMut, Item, Value};
+
+#[derive(Debug, Error)]
+pub enum CargoTomlParserError {
+	#[error("failed to get \"workspace\" section")]
+	FailedToGetWorkspace,
+	#[error("failed to get \"members\" array")]
+	FailedToGetMembers,
+	#[error("failed to get \"dependencies\" section")]
+	FailedToGetDependencies,
+}
+
+pub fn read(path: &Path) -> anyhow::Result<DocumentMut> {
+	std::fs::read_to_string(path)
+		.with_context(|| format!("failed to read {}", path.display()))?
+		.parse::<DocumentMut>()
+		.with_context(|| format!("failed to parse {}", path.display()))
+}
+
+pub fn write(path: &Path, manifest: &DocumentMut) -> anyhow::Result<()> {
+	std::fs::write(path, manifest.to_string())
+		.with_context(|| format!("failed to write {}", path.display()))
+}
+
+pub fn workspace_members(manifest: &DocumentMut) -> Result<&Array, CargoTomlParserError> {
+	manifest
+		.get("workspace")
+		.ok_or(CargoTomlParserError::FailedToGetWorkspace)?
+		.get("members")
+		.and_then(Item::as_array)
+		.ok_or(CargoTomlParserError::FailedToGetMembers)
+}
+
+/// `[workspace] exclude`, empty when it isn't there.
+pub fn workspace_excludes(manifest: &DocumentMut) -> Vec<&str> {
+	manifest
+		.get("workspace")
+		.and_then(|workspace| workspace.get("exclude"))
+		.and_then(Item::as_array)
+		.map(|exclude| exclude.iter().filter_map(Value::as_str).collect())
+		.unwrap_or_default()
+}
+
+pub fn workspace_members_mut(
+	manifest: &mut DocumentMut,
+) -> Result<&mut Array, CargoTomlParserError> {
+	manifest
+		.get_mut("workspace")
+		.ok_or(CargoTomlParserError::FailedToGetWorkspace)?
+		.get_mut("members")
+		.and_then(Item::as_array_mut)
+		.ok_or(CargoTomlParserError::FailedToGetMembers)
+}
+
+/// Inserts `member` before the first day member that comes later (by year, then day), copying
+/// the quoting and whitespace of the members around it. Returns false if it already is a member.
+pub fn add_workspace_member(members: &mut Array, member: &str) -> bool {
+	if members.iter().any(|value| value.as_str() == Some(member)) {
+		return false;
+	}
+
+	let key = day_key(member);
+	let idx = members
+		.iter()
+		.position(|value| match (key, value.as_str().and_then(day_key)) {
+			(Some(key), Some(member_key)) => member_key > key,
+			_ => false,
+		})
+		.unwrap_or(members.len());
+
+	let first_decor = members.get(0).map(|value| value.decor().clone());
+	let decor = members
+		.get(1)
+		.or_else(|| members.get(0))
+		.map(|value| value.decor().clone());
+	let single_quoted = members
+		.iter()
+		.filter_map(|value| match value {
+			Value::String(string) => string.as_repr()?.as_raw().as_str(),
+			_ => None,
+		})
+		.any(|raw| raw.starts_with('\''));
+
+	let value = if single_quoted && !member.contains('\'') {
+		format!("'{}'", member)
+			.parse::<Value>()
+			.unwrap_or_else(|_| Value::from(member))
+	} else {
+		Value::from(member)
+	};
+	let appended = idx == members.len();
+	members.insert_formatted(idx, value);
+
+	if let Some(mut decor) = decor {
+		// A comment after the last member is stored as the array trailer, keep it on its line
+		let trailing = members.trailing().as_str().unwrap_or_default().to_string();
+		match trailing.split_once('\n') {
+			Some((comment, rest)) if appended && comment.trim_start().starts_with('#') => {
+				let prefix = decor
+					.prefix()
+					.and_then(|prefix| prefix.as_str())
+					.unwrap_or_default();
+				decor.set_prefix(format!("{}\n{}", comment, prefix.trim_start_matches('\n')));
+				members.set_trailing(format!("\n{}", rest));
+			}
+			_ => (),
+		}
+
+		*members.get_mut(idx).unwrap().decor_mut() = decor.clone();
+		if idx == 0 {
+			if let Some(first_decor) = first_decor {
+				*members.get_mut(0).unwrap().decor_mut() = first_decor;
+			}
+			*members.get_mut(1).unwrap().decor_mut() = decor;
+		}
+	}
+
+	true
+}
+
+pub fn remove_workspace_member(members: &mut Array, member: &str) -> bool {
+	let idx = match members
+		.iter()
+		.position(|value| value.as_str() == Some(member))
+	{
+		Some(idx) => idx,
+		None => return false,
+	};
+
+	let removed_decor = members.get(idx).map(|value| value.decor().clone());
+	members.remove(idx);
+	// Keep the look of the first element, e.g. no space after `[` in an inline array
+	if idx == 0 {
+		if let (Some(first), Some(decor)) = (members.get_mut(0), removed_decor) {
+			*first.decor_mut() = decor;
+		}
+	}
+
+	true
+}
+
+/// Puts the day members in order, by year and then by day. Other members and the layout of the array stay
+/// where they are. Returns whether anything moved.
+pub fn sort_day_members(members: &mut Array) -> bool {
+	let slots = members
+		.iter()
+		.enumerate()
+		.filter(|(_, value)| value.as_str().and_then(day_key).is_some())
+		.map(|(idx, _)| idx)
+		.collect::<Vec<_>>();
+
+	let mut values = slots
+		.iter()
+		.filter_map(|idx| members.get(*idx).cloned())
+		.collect::<Vec<_>>();
+	values.sort_by_key(|value| value.as_str().and_then(day_key));
+
+	let mut moved = false;
+	for (idx, mut value) in slots.into_iter().zip(values) {
+		let current = members.get(idx).unwrap();
+		if current.as_str() == value.as_str() {
+			continue;
+		}
+		*value.decor_mut() = current.decor().clone();
+		members.replace_formatted(idx, value);
+		moved = true;
+	}
+
+	moved
+}
+
+#[cfg(test)]
+mod tests {
+	use toml_edit::DocumentMut;
+
+	fn add(manifest: &str, member: &str) -> String {
+		let mut manifest = manifest.parse::<DocumentMut>().unwrap();
+		let members = super::workspace_members_mut(&mut manifest).unwrap();
+		super::add_workspace_member(members, member);
+		manifest.to_string()
+	}
+
+	fn remove(manifest: &str, member: &str) -> String {
+		let mut manifest = manifest.parse::<DocumentMut>().unwrap();
+		let members = super::workspace_members_mut(&mut manifest).unwrap();
+		super::remove_workspace_member(members, member);
+		manifest.to_string()
+	}
+
+	const MULTILINE: &str = "# The workspace
+[workspace]
+members = [
+    'aqa_aoc',
+    'day_1',
+    'day_2',
+    'day_10', # double digits
+]
+
+[profile.release]
+debug = true
+";
+
+	#[test]
+	fn add_member_in_numeric_order() {
+		assert_eq!(
+			add(MULTILINE, "day_3"),
+			MULTILINE.replace("    'day_2',\n", "    'day_2',\n    'day_3',\n")
+		);
+		assert_eq!(
+			add(MULTILINE, "day_11"),
+			MULTILINE.replace("double digits\n", "double digits\n    'day_11',\n")
+		);
+	}
+
+	#[test]
+	fn add_member_inline() {
+		assert_eq!(
+			add("[workspace]\nmembers = [\"day_2\", \"day_5\"]\n", "day_1"),
+			"[workspace]\nmembers = [\"day_1\", \"day_2\", \"day_5\"]\n"
+		);
+		assert_eq!(
+			add("[workspace]\nmembers = [\"day_2\", \"day_5\"]\n", "day_3"),
+			"[workspace]\nmembers = [\"day_2\", \"day_3\", \"day_5\"]\n"
+		);
+		assert_eq!(
+			add("[workspace]\nmembers = []\n", "day_1"),
+			"[workspace]\nmembers = [\"day_1\"]\n"
+		);
+	}
+
+	#[test]
+	fn add_existing_member() {
+		assert_eq!(add(MULTILINE, "day_2"), MULTILINE);
+	}
+
+	#[test]
+	fn add_member_by_year() {
+		let manifest =
+			"[workspace]\nmembers = [\"aqa_aoc\", \"year_2021/day_3\", \"year_2022/day_1\"]\n";
+		assert_eq!(
+			add(manifest, "year_2021/day_25"),
+			manifest.replace(
+				"\"year_2021/day_3\", ",
+				"\"year_2021/day_3\", \"year_2021/day_25\", "
+			)
+		);
+		assert_eq!(
+			add(manifest, "year_2022/day_2"),
+			manifest.replace("day_1\"]", "day_1\", \"year_2022/day_2\"]")
+		);
+	}
+
+	#[test]
+	fn remove_member() {
+		assert_eq!(
+			remove(MULTILINE, "day_2"),
+			MULTILINE.replace("    'day_2',\n", "")
+		);
+		assert_eq!(
+			remove("[workspace]\nmembers = [\"day_2\", \"day_5\"]\n", "day_2"),
+			"[workspace]\nmembers = [\"day_5\"]\n"
+		);
+	}
+
+	#[test]
+	fn sort_members() {
+		let mut manifest = "[workspace]\nmembers = [\n    'day_10',\n    'aqa_aoc',\n    'day_2', # two\n    'day_1',\n]\n"
+			.parse::<DocumentMut>()
+			.unwrap();
+		let members = super::workspace_members_mut(&mut manifest).unwrap();
+		assert!(super::sort_day_members(members));
+		assert_eq!(
+			manifest.to_string(),
+			"[workspace]\nmembers = [\n    'day_1',\n    'aqa_aoc',\n    'day_2', # two\n    'day_10',\n]\n"
+		);
+
+		let members = super::workspace_members_mut(&mut manifest).unwrap();
+		assert!(!super::sort_day_members(members));
+	}
+}
@@ -0,0 +1,174 @@
+use crate::html::{self, Token};
+use std::path::Path;
+
+/// Converts every `<article class="day-desc">` of the puzzle page to Markdown. Part 2 shows up
+/// as a second article once part 1 is solved.
+pub fn puzzle_to_markdown(puzzle_html: &str, addr: &str) -> Option<String> {
+	let tokens = html::tokenize(puzzle_html);
+	let articles = html::find_elements(&tokens, "article", Some("day-desc"));
+	if articles.is_empty() {
+		return None;
+	}
+
+	let markdown = articles
+		.into_iter()
+		.map(|article| article_to_markdown(article, addr))
+		.collect::<Vec<_>>()
+		.join("\n");
+
+	Some(markdown)
+}
+
+/// Writes the README if it changed. Returns whether anything was written.
+pub fn save_readme(path: &Path, markdown: &str) -> std::io::Result<bool> {
+	if std::fs::read_to_string(path).ok().as_deref() == Some(markdown) {
+		return Ok(false);
+	}
+	std::fs::write(path, markdown)?;
+	Ok(true)
+}
+
+fn article_to_markdown(tokens: &[Token<'_>], addr: &str) -> String {
+	let mut md = String::new();
+
+	let mut in_pre = false;
+	// Inline code is buffered, so that `<code><em>x</em></code>` can become *`x`*
+	let mut code: Option<(String, bool)> = None;
+	let mut links = Vec::new();
+
+	for token in tokens {
+		match token {
+			Token::Start { name, .. } => match *name {
+				"h2" => md.push_str("## "),
+				"pre" => {
+					in_pre = true;
+					md.push_str("```\n");
+				}
+				"code" if !in_pre => code = Some((String::new(), false)),
+				"em" => match &mut code {
+					Some((_, emphasized)) => *emphasized = true,
+					None if !in_pre => md.push('*'),
+					None => (),
+				},
+				"a" => {
+					let href = token.attr("href").unwrap_or_default();
+					let href = if href.starts_with('/') {
+						format!("{}{}", addr, href)
+					} else {
+						href.to_string()
+					};
+					links.push(href);
+					md.push('[');
+				}
+				"li" => md.push_str("- "),
+				_ => (),
+			},
+			Token::End { name } => match *name {
+				"h2" | "p" => md.push_str("\n\n"),
+				"pre" => {
+					in_pre = false;
+					if !md.ends_with('\n') {
+						md.push('\n');
+					}
+					md.push_str("```\n\n");
+				}
+				"code" if !in_pre => {
+					if let Some((text, emphasized)) = code.take() {
+						let marker = if emphasized { "*" } else { "" };
+						md.push_str(&format!("{marker}`{text}`{marker}"));
+					}
+				}
+				"em" if code.is_none() && !in_pre => md.push('*'),
+				"a" => {
+					let href = links.pop().unwrap_or_default();
+					md.push_str(&format!("]({})", href));
+				}
+				"li" => md.push('\n'),
+				"ul" => md.push('\n'),
+				_ => (),
+			},
+			Token::Text(text) => {
+				if let Some((code, _)) = &mut code {
+					code.push_str(text);
+				} else if in_pre
+					|| !text.trim().is_empty()
+					|| !(md.is_empty() || md.ends_with('\n'))
+				{
+					md.push_str(text);
+				}
+			}
+		}
+	}
+
+	let mut md = md.trim_end().to_string();
+	md.push('\n');
+	md
+}
+
+#[cfg(test)]
+mod tests {
+	const PUZZLE: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<head><title>Day 1 - Advent of Code 2022</title></head>
+<body>
+<main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2><p>The Elves take turns writing down the number of <a href="https://en.wikipedia.org/wiki/Calorie">Calories</a> contained by the various meals.</p>
+<p>For example:</p>
+<pre><code>1000
+2000
+
+3000
+</code></pre>
+<ul>
+<li>The first Elf is carrying <em>6000</em> Calories.</li>
+<li>The second Elf is carrying <code>4000</code> Calories.</li>
+</ul>
+<p>In the example above, this is <em><code>24000</code></em> or <code><em>24000</em></code> (carried by the fourth Elf).</p>
+</article>
+<p>To begin, <a href="1/input" target="_blank">get your puzzle input</a>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>See <a href="/2022/day/1/input">your input</a> &amp; find the <em>top three</em>.</p>
+</article>
+</main>
+</body>
+</html>
+"#;
+
+	const MARKDOWN: &str = "## --- Day 1: Calorie Counting ---
+
+The Elves take turns writing down the number of [Calories](https://en.wikipedia.org/wiki/Calorie) contained by the various meals.
+
+For example:
+
+```
+1000
+2000
+
+3000
+```
+
+- The first Elf is carrying *6000* Calories.
+- The second Elf is carrying `4000` Calories.
+
+In the example above, this is *`24000`* or *`24000`* (carried by the fourth Elf).
+
+## --- Part Two ---
+
+See [your input](https://adventofcode.com/2022/day/1/input) & find the *top three*.
+";
+
+	#[test]
+	fn puzzle_to_markdown() {
+		assert_eq!(
+			super::puzzle_to_markdown(PUZZLE, "https://adventofcode.com").unwrap(),
+			MARKDOWN
+		);
+	}
+
+	#[test]
+	fn puzzle_to_markdown_without_articles() {
+		assert_eq!(
+			super::puzzle_to_markdown("<html></html>", "https://adventofcode.com"),
+			None
+		);
+	}
+}
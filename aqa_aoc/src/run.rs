@@ -0,0 +1,375 @@
+//! Running the solution of a day. Every `main` prints a `Part 1: ...` and a `Part 2: ...` line,
+//! those are compared to answers.toml. A `main` built on `aoc_common::main` also runs only the
+//! part in [PART_ENV], and prints a `Time N: ...` line before each part when [TIMINGS_ENV] is set.
+
+use crate::answers::{Ledger, PartLedger, Refusal};
+use crate::cache;
+use crate::layout::DayCrate;
+use anyhow::{bail, Context};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// Same as `aoc_common::PART_ENV`.
+pub const PART_ENV: &str = "AOC_PART";
+
+/// Same as `aoc_common::TIMINGS_ENV`.
+pub const TIMINGS_ENV: &str = "AOC_TIMINGS";
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PartResult {
+	pub part: u32,
+	pub answer: String,
+	/// Time the solution took for the part, as it reports it. `None` when it doesn't, when its
+	/// `main` isn't `aoc_common::main`.
+	pub elapsed: Option<Duration>,
+}
+
+/// How an answer compares to answers.toml.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check {
+	/// Nothing is known about the answer yet
+	Unknown,
+	Correct,
+	Wrong {
+		expected: String,
+	},
+	/// Not solved yet, but AoC said no to this answer before
+	KnownWrong(Refusal),
+}
+
+pub fn check(ledger: &PartLedger, answer: &str) -> Check {
+	match ledger.check(answer) {
+		Ok(()) => Check::Unknown,
+		Err(Refusal::AlreadyCorrect) => Check::Correct,
+		Err(Refusal::AlreadySolved { answer }) => Check::Wrong { expected: answer },
+		Err(refusal) => Check::KnownWrong(refusal),
+	}
+}
+
+/// Builds the day crate, without running it, and returns the path of its executable.
+pub fn build(day_crate: &DayCrate, release: bool) -> anyhow::Result<PathBuf> {
+	let mut cargo_build = Command::new("cargo");
+	cargo_build.args([
+		"build",
+		"--quiet",
+		"--message-format=json-render-diagnostics",
+		"-p",
+		&day_crate.name(),
+	]);
+	if release {
+		cargo_build.arg("--release");
+	}
+
+	let output = cargo_build
+		.stderr(Stdio::inherit())
+		.output()
+		.context("failed to run cargo build")?;
+	if !output.status.success() {
+		bail!(
+			"cargo build failed with code {}",
+			output.status.code().unwrap_or_default()
+		);
+	}
+
+	String::from_utf8_lossy(&output.stdout)
+		.lines()
+		.filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+		.filter(|message| message["reason"] == "compiler-artifact")
+		.find_map(|message| message["executable"].as_str().map(PathBuf::from))
+		.with_context(|| {
+			format!(
+				"cargo build didn't produce an executable for {}",
+				day_crate.name()
+			)
+		})
+}
+
+/// Runs the tests of the day crate, showing their output. `false` when a test fails.
+pub fn test(day_crate: &DayCrate) -> anyhow::Result<bool> {
+	let status = Command::new("cargo")
+		.args(["test", "--quiet", "-p", &day_crate.name()])
+		.status()
+		.context("failed to run cargo test")?;
+	Ok(status.success())
+}
+
+/// Runs the solution of the day from the workspace root, where it expects to find its input.
+/// `only_part` asks it to run only that part. `on_part` is called as soon as a part is printed,
+/// other output is passed through.
+pub fn run(
+	day_crate: &DayCrate,
+	only_part: Option<u32>,
+	release: bool,
+	on_part: impl FnMut(PartResult),
+) -> anyhow::Result<()> {
+	let input_path = cache::input_path(&day_crate.dir());
+	if !input_path.exists() {
+		bail!(
+			"{} is missing, download it with `aqa_aoc fetch --day {} --year {}`",
+			input_path.display(),
+			day_crate.day,
+			day_crate.year
+		);
+	}
+
+	let executable = build(day_crate, release)?;
+
+	let mut command = Command::new(&executable);
+	command.env(TIMINGS_ENV, "1");
+	if let Some(part) = only_part {
+		command.env(PART_ENV, part.to_string());
+	}
+	let mut child = command
+		.stdout(Stdio::piped())
+		.spawn()
+		.with_context(|| format!("failed to run {}", executable.display()))?;
+
+	// SAFETY: stdout is piped
+	read_output(BufReader::new(child.stdout.take().unwrap()), on_part)?;
+
+	let status = child.wait()?;
+	if !status.success() {
+		bail!(
+			"{} failed with code {}",
+			display_name(&executable),
+			status.code().unwrap_or_default()
+		);
+	}
+
+	Ok(())
+}
+
+/// What a run of the solution printed.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Checked {
+	pub answers: BTreeMap<u32, String>,
+	/// Parts that don't match answers.toml
+	pub wrong: Vec<u32>,
+}
+
+/// [run], checking every part against answers.toml. `on_part` gets each part with its [Check] as
+/// soon as it's printed. `only_part` leaves out the other part, also when the solution runs it
+/// anyway.
+pub fn run_and_check(
+	day_crate: &DayCrate,
+	only_part: Option<u32>,
+	release: bool,
+	mut on_part: impl FnMut(&PartResult, &Check),
+) -> anyhow::Result<Checked> {
+	let ledger = Ledger::load(&day_crate.dir())?;
+
+	let mut checked = Checked::default();
+	run(day_crate, only_part, release, |result| {
+		if only_part.is_some_and(|part| part != result.part) {
+			return;
+		}
+
+		let check = check(ledger.part(result.part), &result.answer);
+		if matches!(check, Check::Wrong { .. } | Check::KnownWrong(_)) {
+			checked.wrong.push(result.part);
+		}
+		on_part(&result, &check);
+		checked.answers.insert(result.part, result.answer);
+	})?;
+
+	Ok(checked)
+}
+
+/// Runs the tests, then [run_and_check] if they pass. `None` when the tests fail.
+pub fn test_and_run(
+	day_crate: &DayCrate,
+	release: bool,
+	on_part: impl FnMut(&PartResult, &Check),
+) -> anyhow::Result<Option<Checked>> {
+	if !test(day_crate)? {
+		return Ok(None);
+	}
+	run_and_check(day_crate, None, release, on_part).map(Some)
+}
+
+/// Calls `on_part` for each part in the output of the solution, and prints the other lines. A
+/// `Part N:` line without an answer starts a multi-line one, up to the next `Part` or `Time` line.
+fn read_output(output: impl BufRead, mut on_part: impl FnMut(PartResult)) -> anyhow::Result<()> {
+	let mut times = [None; 2];
+	// The part whose answer spans the lines being read, and those lines
+	let mut block: Option<(u32, Vec<String>)> = None;
+	let mut answered = |part: u32, answer: String, times: &mut [Option<Duration>; 2]| {
+		on_part(PartResult {
+			part,
+			answer,
+			elapsed: times[part as usize - 1].take(),
+		})
+	};
+
+	for line in output.lines() {
+		let line = line.context("failed to read the output of the solution")?;
+		let time = parse_time_line(&line);
+		let part = parse_part_line(&line);
+		if time.is_some() || part.is_some() {
+			if let Some((part, lines)) = block.take() {
+				answered(part, lines.join("\n").trim_end().to_string(), &mut times);
+			}
+		}
+
+		if let Some((part, elapsed)) = time {
+			times[part as usize - 1] = Some(elapsed);
+		} else if let Some((part, answer)) = part {
+			if answer.is_empty() {
+				block = Some((part, Vec::new()));
+			} else {
+				answered(part, answer.to_string(), &mut times);
+			}
+		} else if let Some((_, lines)) = &mut block {
+			lines.push(line);
+		} else {
+			println!("{}", line);
+		}
+	}
+	if let Some((part, lines)) = block {
+		answered(part, lines.join("\n").trim_end().to_string(), &mut times);
+	}
+
+	Ok(())
+}
+
+fn display_name(executable: &Path) -> String {
+	executable
+		.file_name()
+		.map(|name| name.to_string_lossy().into_owned())
+		.unwrap_or_else(|| executable.display().to_string())
+}
+
+/// `Part 1: 1234` as `(1, "1234")`.
+fn parse_part_line(line: &str) -> Option<(u32, &str)> {
+	let (part, answer) = line.strip_prefix("Part ")?.split_once(':')?;
+	let part = part
+		.trim()
+		.parse()
+		.ok()
+		.filter(|part| (1..=2).contains(part))?;
+	Some((part, answer.trim()))
+}
+
+/// `Time 1: 1500ns` as `(1, 1.5µs)`.
+fn parse_time_line(line: &str) -> Option<(u32, Duration)> {
+	let (part, nanos) = line.strip_prefix("Time ")?.split_once(':')?;
+	let part = part
+		.trim()
+		.parse()
+		.ok()
+		.filter(|part| (1..=2).contains(part))?;
+	let nanos = nanos.trim().strip_suffix("ns")?.parse().ok()?;
+	Some((part, Duration::from_nanos(nanos)))
+}
+
+/// Short human readable duration, e.g. `850µs`, `12.34ms` or `1.50s`.
+pub fn format_duration(duration: Duration) -> String {
+	if duration < Duration::from_millis(1) {
+		format!("{}µs", duration.as_micros())
+	} else if duration < Duration::from_secs(1) {
+		format!("{:.2}ms", duration.as_secs_f64() * 1000.0)
+	} else {
+		format!("{:.2}s", duration.as_secs_f64())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Check, PartResult};
+	use crate::answers::{PartLedger, Refusal, Verdict};
+	use std::time::Duration;
+
+	#[test]
+	fn parse_part_line() {
+		assert_eq!(super::parse_part_line("Part 1: 1234"), Some((1, "1234")));
+		assert_eq!(
+			super::parse_part_line("Part 2:  abc def "),
+			Some((2, "abc def"))
+		);
+		assert_eq!(super::parse_part_line("Part 3: 1"), None);
+		assert_eq!(super::parse_part_line("Parsing: 1"), None);
+		assert_eq!(super::parse_part_line("debug Part 1: 1"), None);
+	}
+
+	#[test]
+	fn read_output() {
+		let output = "Time 1: 1500ns\nPart 1: 13140\nTime 2: 2000ns\nPart 2:\n##..#\n#..##\n";
+		let mut parts = Vec::new();
+		super::read_output(output.as_bytes(), |result| parts.push(result)).unwrap();
+		assert_eq!(
+			parts,
+			vec![
+				PartResult {
+					part: 1,
+					answer: "13140".to_string(),
+					elapsed: Some(Duration::from_nanos(1500)),
+				},
+				PartResult {
+					part: 2,
+					answer: "##..#\n#..##".to_string(),
+					elapsed: Some(Duration::from_nanos(2000)),
+				},
+			]
+		);
+
+		let output = "Part 1:\n#.\n.#\nPart 2: 7\n";
+		let mut answers = Vec::new();
+		super::read_output(output.as_bytes(), |result| answers.push(result.answer)).unwrap();
+		assert_eq!(answers, vec!["#.\n.#", "7"]);
+	}
+
+	#[test]
+	fn parse_time_line() {
+		assert_eq!(
+			super::parse_time_line("Time 2: 1500ns"),
+			Some((2, Duration::from_nanos(1500)))
+		);
+		assert_eq!(super::parse_time_line("Time 3: 1500ns"), None);
+		assert_eq!(super::parse_time_line("Time 1: 1.5ms"), None);
+		assert_eq!(super::parse_time_line("Time flies"), None);
+	}
+
+	#[test]
+	fn check() {
+		let mut ledger = PartLedger::default();
+		assert_eq!(super::check(&ledger, "10"), Check::Unknown);
+
+		ledger.record("10", Verdict::TooLow, 0);
+		assert_eq!(
+			super::check(&ledger, "10"),
+			Check::KnownWrong(Refusal::KnownWrong {
+				verdict: Verdict::TooLow,
+				at: 0
+			})
+		);
+		assert_eq!(
+			super::check(&ledger, "5"),
+			Check::KnownWrong(Refusal::TooLow { bound: 10 })
+		);
+
+		ledger.record("20", Verdict::Correct, 0);
+		assert_eq!(super::check(&ledger, "20"), Check::Correct);
+		assert_eq!(
+			super::check(&ledger, "21"),
+			Check::Wrong {
+				expected: "20".to_string()
+			}
+		);
+	}
+
+	#[test]
+	fn format_duration() {
+		assert_eq!(super::format_duration(Duration::from_micros(850)), "850µs");
+		assert_eq!(
+			super::format_duration(Duration::from_micros(12_346)),
+			"12.35ms"
+		);
+		assert_eq!(
+			super::format_duration(Duration::from_millis(1_500)),
+			"1.50s"
+		);
+	}
+}
@@ -0,0 +1,227 @@
+//! Where the session cookie comes from, and checking that AoC still accepts it.
+
+use crate::html::{self, Token};
+use anyhow::{bail, Context};
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Takes precedence over every session setting in aoc_cfg.toml.
+pub static SESSION_ENV: &str = "AQA_AOC_SESSION";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+	Env,
+	Config,
+	Command(String),
+	File(PathBuf),
+}
+
+impl Display for Source {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Source::Env => write!(f, "the {} variable", SESSION_ENV),
+			Source::Config => write!(f, "`session_cookie` in aoc_cfg.toml"),
+			Source::Command(command) => write!(f, "the output of `{}`", command),
+			Source::File(path) => write!(f, "{}", path.display()),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+	pub cookie: String,
+	pub source: Source,
+}
+
+/// Session settings of aoc_cfg.toml.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Settings<'a> {
+	pub cookie: Option<&'a str>,
+	pub command: Option<&'a str>,
+	pub file: Option<&'a Path>,
+}
+
+/// Takes the first source that is set: the environment variable (`env`), `session_cookie`,
+/// `session_command`, `session_file` and finally [default_file].
+pub fn resolve(env: Option<String>, settings: Settings<'_>) -> anyhow::Result<Session> {
+	if let Some(cookie) = env.filter(|cookie| !cookie.trim().is_empty()) {
+		return Ok(Session {
+			cookie: cookie.trim().to_string(),
+			source: Source::Env,
+		});
+	}
+
+	let (cookie, source) = if let Some(cookie) = settings.cookie {
+		(cookie.to_string(), Source::Config)
+	} else if let Some(command) = settings.command {
+		(run_command(command)?, Source::Command(command.to_string()))
+	} else if let Some(path) = settings.file {
+		(read_file(path)?, Source::File(path.to_path_buf()))
+	} else {
+		match default_file().filter(|path| path.is_file()) {
+			Some(path) => (read_file(&path)?, Source::File(path)),
+			None => bail!(
+				"no session cookie found. Set {}, `session_cookie`, `session_command` or `session_file` in aoc_cfg.toml, or save it to {}",
+				SESSION_ENV,
+				default_file()
+					.map(|path| path.display().to_string())
+					.unwrap_or_else(|| "~/.config/aqa_aoc/session".to_string())
+			),
+		}
+	};
+
+	let cookie = cookie.trim().to_string();
+	if cookie.is_empty() {
+		bail!("the session cookie from {} is empty", source);
+	}
+
+	Ok(Session { cookie, source })
+}
+
+/// `aqa_aoc/session` in the user's config directory.
+pub fn default_file() -> Option<PathBuf> {
+	let config_dir = match std::env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+		Some(dir) => PathBuf::from(dir),
+		None if cfg!(windows) => PathBuf::from(std::env::var_os("APPDATA")?),
+		None => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+	};
+	Some(config_dir.join("aqa_aoc").join("session"))
+}
+
+fn read_file(path: &Path) -> anyhow::Result<String> {
+	std::fs::read_to_string(path)
+		.with_context(|| format!("failed to read the session cookie from {}", path.display()))
+}
+
+fn run_command(command: &str) -> anyhow::Result<String> {
+	let output = if cfg!(windows) {
+		Command::new("cmd").args(["/C", command]).output()
+	} else {
+		Command::new("sh").args(["-c", command]).output()
+	}
+	.with_context(|| format!("failed to run `{}`", command))?;
+
+	if !output.status.success() {
+		bail!(
+			"`{}` failed with code {}: {}",
+			command,
+			output.status.code().unwrap_or_default(),
+			String::from_utf8_lossy(&output.stderr).trim()
+		);
+	}
+
+	String::from_utf8(output.stdout).with_context(|| format!("`{}` printed invalid UTF-8", command))
+}
+
+/// Name of the logged in user, as shown in the header of every AoC page. `None` when the page
+/// was served to a visitor that isn't logged in, which is what an expired session gets.
+pub fn logged_in_user(page: &str) -> Option<String> {
+	let tokens = html::tokenize(page);
+	let user = html::find_elements(&tokens, "div", Some("user"))
+		.into_iter()
+		.next()?;
+
+	// The name comes first, followed by badges and the star count
+	let name = user
+		.iter()
+		.take_while(|token| !matches!(token, Token::Start { .. }))
+		.filter_map(|token| match token {
+			Token::Text(text) => Some(text.as_str()),
+			_ => None,
+		})
+		.collect::<String>();
+	let name = name.trim();
+	(!name.is_empty()).then(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Session, Settings, Source};
+	use std::path::Path;
+
+	#[test]
+	fn resolve_order() {
+		let settings = Settings {
+			cookie: Some("from_cfg"),
+			command: Some("echo from_command"),
+			file: Some(Path::new("/nonexistent/session")),
+		};
+
+		assert_eq!(
+			super::resolve(Some("from_env\n".to_string()), settings).unwrap(),
+			Session {
+				cookie: "from_env".to_string(),
+				source: Source::Env
+			}
+		);
+		assert_eq!(
+			super::resolve(Some(String::new()), settings)
+				.unwrap()
+				.source,
+			Source::Config
+		);
+
+		let settings = Settings {
+			cookie: None,
+			..settings
+		};
+		assert_eq!(
+			super::resolve(None, settings).unwrap(),
+			Session {
+				cookie: "from_command".to_string(),
+				source: Source::Command("echo from_command".to_string())
+			}
+		);
+
+		let settings = Settings {
+			command: None,
+			..settings
+		};
+		assert!(super::resolve(None, settings).is_err());
+	}
+
+	#[test]
+	fn session_file() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("session");
+		std::fs::write(&path, "53616c7465645f5f\n").unwrap();
+
+		let settings = Settings {
+			file: Some(&path),
+			..Settings::default()
+		};
+		assert_eq!(
+			super::resolve(None, settings).unwrap(),
+			Session {
+				cookie: "53616c7465645f5f".to_string(),
+				source: Source::File(path.clone())
+			}
+		);
+
+		std::fs::write(&path, "\n").unwrap();
+		assert!(super::resolve(None, settings).is_err());
+	}
+
+	#[test]
+	fn logged_in_user() {
+		assert_eq!(
+			super::logged_in_user(
+				r#"<header><div><div class="user">aQaTL <a href="/2022/support" class="supporter-badge">AoC++</a> <span class="star-count">42*</span></div></div></header>"#
+			),
+			Some("aQaTL".to_string())
+		);
+		assert_eq!(
+			super::logged_in_user(
+				r#"<div class="user">(anonymous user #123456) <span class="star-count">2*</span></div>"#
+			),
+			Some("(anonymous user #123456)".to_string())
+		);
+		assert_eq!(
+			super::logged_in_user(
+				r#"<header><div><ul><li><a href="/auth/login">[Log In]</a></li></ul></div></header>"#
+			),
+			None
+		);
+	}
+}
@@ -0,0 +1,205 @@
+use crate::answers::{Ledger, Refusal, Verdict};
+use crate::config::AocCfg;
+use crate::date;
+use crate::error::AocError;
+use crate::html;
+use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+	Correct,
+	Wrong,
+	TooHigh,
+	TooLow,
+	RateLimited { wait: Duration },
+	AlreadySolved,
+}
+
+impl Display for Outcome {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Outcome::Correct => write!(f, "That's the right answer!"),
+			Outcome::Wrong => write!(f, "That's not the right answer"),
+			Outcome::TooHigh => write!(f, "That's not the right answer, it's too high"),
+			Outcome::TooLow => write!(f, "That's not the right answer, it's too low"),
+			Outcome::RateLimited { wait } => write!(
+				f,
+				"You gave an answer too recently, wait {}s before trying again",
+				wait.as_secs()
+			),
+			Outcome::AlreadySolved => write!(f, "This part is already solved"),
+		}
+	}
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Submission {
+	/// Not sent, answers.toml says it's wrong
+	Refused(Refusal),
+	Sent {
+		outcome: Outcome,
+		/// Why it would have been refused, when it was sent anyway
+		forced_past: Option<Refusal>,
+		/// The outcome was recorded in answers.toml. It isn't when the day has no crate.
+		recorded: bool,
+	},
+}
+
+/// Submits `answer` unless answers.toml knows it's wrong, `force` submits it anyway. The outcome
+/// is recorded in answers.toml. Waiting for the rate limit is an [AocError::RateLimited].
+pub fn submit(
+	cfg: &AocCfg,
+	day: u32,
+	part: u32,
+	answer: &str,
+	force: bool,
+) -> anyhow::Result<Submission> {
+	let answer = answer.trim();
+	let crate_dir = cfg.day_crate(day).dir();
+	let mut ledger = Ledger::load(&crate_dir)?;
+
+	let forced_past = match ledger.part(part).check(answer) {
+		Ok(()) => None,
+		Err(refusal) if force => Some(refusal),
+		Err(refusal) => return Ok(Submission::Refused(refusal)),
+	};
+
+	let outcome = cfg.client()?.submit(cfg.year, day, part, answer)?;
+	if let Outcome::RateLimited { wait } = outcome {
+		return Err(AocError::RateLimited { wait: Some(wait) }.into());
+	}
+
+	let mut recorded = false;
+	if let Some(verdict) = Verdict::from_outcome(outcome) {
+		if crate_dir.is_dir() {
+			ledger
+				.part_mut(part)
+				.record(answer, verdict, date::now_unix());
+			ledger.save(&crate_dir)?;
+			recorded = true;
+		}
+	}
+
+	Ok(Submission::Sent {
+		outcome,
+		forced_past,
+		recorded,
+	})
+}
+
+/// Figures out the outcome from the `<article>` AoC puts in the answer page.
+pub fn parse_outcome(html: &str) -> anyhow::Result<Outcome> {
+	let text = article_text(html)
+		.ok_or_else(|| AocError::UnexpectedHtml("no <article> in the answer page".to_string()))?;
+
+	let outcome = if text.contains("That's the right answer") {
+		Outcome::Correct
+	} else if text.contains("You gave an answer too recently") {
+		Outcome::RateLimited {
+			wait: parse_wait_time(&text).unwrap_or_default(),
+		}
+	} else if text.contains("You don't seem to be solving the right level") {
+		Outcome::AlreadySolved
+	} else if text.contains("That's not the right answer") {
+		if text.contains("your answer is too high") {
+			Outcome::TooHigh
+		} else if text.contains("your answer is too low") {
+			Outcome::TooLow
+		} else {
+			Outcome::Wrong
+		}
+	} else {
+		return Err(
+			AocError::UnexpectedHtml(format!("unrecognized answer response: {}", text)).into(),
+		);
+	};
+
+	Ok(outcome)
+}
+
+fn article_text(html: &str) -> Option<String> {
+	let tokens = html::tokenize(html);
+	html::find_elements(&tokens, "article", None)
+		.first()
+		.map(|article| html::text(article))
+}
+
+/// Parses the "You have 1m 23s left to wait." sentence.
+fn parse_wait_time(text: &str) -> Option<Duration> {
+	let (_, rest) = text.split_once("You have ")?;
+	let (wait, _) = rest.split_once(" left to wait")?;
+
+	let mut secs = 0;
+	for part in wait.split_whitespace() {
+		let (value, multiplier) = if let Some(v) = part.strip_suffix('h') {
+			(v, 3600)
+		} else if let Some(v) = part.strip_suffix('m') {
+			(v, 60)
+		} else if let Some(v) = part.strip_suffix('s') {
+			(v, 1)
+		} else {
+			return None;
+		};
+		secs += value.parse::<u64>().ok()? * multiplier;
+	}
+
+	Some(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Outcome;
+	use std::time::Duration;
+
+	fn page(article: &str) -> String {
+		format!(
+			"<!DOCTYPE html><html><body><main><article>{}</article></main></body></html>",
+			article
+		)
+	}
+
+	#[test]
+	fn parse_correct() {
+		let html = page("<p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to collecting enough star fruit.</p>");
+		assert_eq!(super::parse_outcome(&html).unwrap(), Outcome::Correct);
+	}
+
+	#[test]
+	fn parse_wrong() {
+		let html = page("<p>That's not the right answer.  If you're stuck, make sure you're using the full input data; please wait one minute before trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a></p>");
+		assert_eq!(super::parse_outcome(&html).unwrap(), Outcome::Wrong);
+	}
+
+	#[test]
+	fn parse_too_high_and_too_low() {
+		let html = page("<p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.</p>");
+		assert_eq!(super::parse_outcome(&html).unwrap(), Outcome::TooHigh);
+
+		let html = page("<p>That's not the right answer; your answer is too low.  If you're stuck, make sure you're using the full input data.</p>");
+		assert_eq!(super::parse_outcome(&html).unwrap(), Outcome::TooLow);
+	}
+
+	#[test]
+	fn parse_rate_limited() {
+		let html = page("<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait. <a href=\"/2022/day/1\">[Return to Day 1]</a></p>");
+		assert_eq!(
+			super::parse_outcome(&html).unwrap(),
+			Outcome::RateLimited {
+				wait: Duration::from_secs(83)
+			}
+		);
+	}
+
+	#[test]
+	fn parse_already_solved() {
+		let html = page("<p>You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2022/day/1\">[Return to Day 1]</a></p>");
+		assert_eq!(super::parse_outcome(&html).unwrap(), Outcome::AlreadySolved);
+	}
+
+	#[test]
+	fn parse_unknown() {
+		assert!(super::parse_outcome("<html></html>").is_err());
+		assert!(super::parse_outcome(&page("<p>Something else</p>")).is_err());
+	}
+}
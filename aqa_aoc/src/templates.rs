@@ -0,0 +1,197 @@
+use crate::layout::DayCrate;
+use anyhow::Context;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File in the template directory that holds the tests module. Unlike the other files it is not
+/// copied into the crate, but appended to `src/lib.rs` (or `src/main.rs` without one) once the
+/// example is known.
+pub const TESTS_TEMPLATE: &str = "tests.rs";
+
+const DEFAULT_LIB_RS: &str = include_str!("../templates/default/src/lib.rs");
+const DEFAULT_MAIN_RS: &str = include_str!("../templates/default/src/main.rs");
+const DEFAULT_TESTS: &str = include_str!("../templates/default/tests.rs");
+
+/// Values for the `{{name}}` placeholders.
+#[derive(Debug, Clone)]
+pub struct Vars(Vec<(&'static str, String)>);
+
+impl Vars {
+	pub fn new(day_crate: &DayCrate) -> Vars {
+		Vars(vec![
+			("day", day_crate.day.to_string()),
+			("year", day_crate.year.to_string()),
+			("crate_name", day_crate.name()),
+			("input_path", format!("{}/input.txt", day_crate.member())),
+		])
+	}
+
+	pub fn with(mut self, name: &'static str, value: impl Into<String>) -> Vars {
+		self.0.push((name, value.into()));
+		self
+	}
+
+	pub fn render(&self, text: &str) -> String {
+		self.0.iter().fold(text.to_string(), |text, (name, value)| {
+			text.replace(&format!("{{{{{}}}}}", name), value)
+		})
+	}
+}
+
+#[derive(Debug)]
+pub struct Template {
+	/// Paths relative to the crate root
+	files: Vec<(PathBuf, String)>,
+	tests: String,
+}
+
+impl Default for Template {
+	fn default() -> Self {
+		Template {
+			files: vec![
+				(Path::new("src").join("lib.rs"), DEFAULT_LIB_RS.to_string()),
+				(
+					Path::new("src").join("main.rs"),
+					DEFAULT_MAIN_RS.to_string(),
+				),
+			],
+			tests: DEFAULT_TESTS.to_string(),
+		}
+	}
+}
+
+impl Template {
+	/// Loads the user template from `dir`, or the built-in one. A user template without
+	/// `tests.rs` gets the built-in tests module.
+	pub fn load(dir: Option<&Path>) -> anyhow::Result<Template> {
+		let dir = match dir {
+			Some(dir) => dir,
+			None => return Ok(Template::default()),
+		};
+
+		let mut files = Vec::new();
+		collect_files(dir, dir, &mut files)
+			.with_context(|| format!("failed to read template {}", dir.display()))?;
+
+		let tests = match files
+			.iter()
+			.position(|(path, _)| path == Path::new(TESTS_TEMPLATE))
+		{
+			Some(idx) => files.remove(idx).1,
+			None => DEFAULT_TESTS.to_string(),
+		};
+
+		Ok(Template { files, tests })
+	}
+
+	pub fn tests(&self) -> &str {
+		&self.tests
+	}
+
+	/// Renders every file into `crate_dir`, overwriting what `cargo new` generated.
+	pub fn render_into(&self, crate_dir: &Path, vars: &Vars) -> anyhow::Result<()> {
+		for (path, contents) in &self.files {
+			let path = crate_dir.join(path);
+			if let Some(parent) = path.parent() {
+				fs::create_dir_all(parent)?;
+			}
+			fs::write(&path, vars.render(contents))
+				.with_context(|| format!("failed to write {}", path.display()))?;
+		}
+		Ok(())
+	}
+}
+
+fn collect_files(
+	root: &Path,
+	dir: &Path,
+	files: &mut Vec<(PathBuf, String)>,
+) -> anyhow::Result<()> {
+	let mut entries = fs::read_dir(dir)?
+		.map(|entry| entry.map(|entry| entry.path()))
+		.collect::<Result<Vec<_>, _>>()?;
+	entries.sort();
+
+	for path in entries {
+		if path.is_dir() {
+			collect_files(root, &path, files)?;
+		} else {
+			let contents = fs::read_to_string(&path)
+				.with_context(|| format!("failed to read {}", path.display()))?;
+			files.push((path.strip_prefix(root)?.to_path_buf(), contents));
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Template, Vars};
+	use crate::layout::Layout;
+	use std::fs;
+	use std::path::Path;
+
+	#[test]
+	fn render_default_template() {
+		let dir = tempfile::tempdir().unwrap();
+		let vars = Vars::new(&Layout::Flat.day_crate(2022, 7));
+
+		Template::default().render_into(dir.path(), &vars).unwrap();
+
+		let lib_rs = fs::read_to_string(dir.path().join("src/lib.rs")).unwrap();
+		assert!(lib_rs
+			.starts_with("//! Advent of Code 2022, day 7: https://adventofcode.com/2022/day/7\n"));
+		assert!(lib_rs.contains("\tconst DAY: u32 = 7;\n"));
+		assert!(!lib_rs.contains("{{"));
+
+		let main_rs = fs::read_to_string(dir.path().join("src/main.rs")).unwrap();
+		assert!(main_rs.contains("aoc_common::main::<day_7::Day>(env!(\"CARGO_MANIFEST_DIR\"))"));
+	}
+
+	#[test]
+	fn load_user_template() {
+		let template_dir = tempfile::tempdir().unwrap();
+		fs::create_dir_all(template_dir.path().join("src")).unwrap();
+		fs::write(
+			template_dir.path().join("src/main.rs"),
+			"fn main() { println!(\"{{crate_name}}\"); }\n",
+		)
+		.unwrap();
+		fs::write(template_dir.path().join("notes.md"), "# Day {{day}}\n").unwrap();
+
+		let template = Template::load(Some(template_dir.path())).unwrap();
+		assert_eq!(
+			template.tests(),
+			include_str!("../templates/default/tests.rs")
+		);
+
+		let dir = tempfile::tempdir().unwrap();
+		template
+			.render_into(dir.path(), &Vars::new(&Layout::Flat.day_crate(2022, 3)))
+			.unwrap();
+
+		assert_eq!(
+			fs::read_to_string(dir.path().join("src/main.rs")).unwrap(),
+			"fn main() { println!(\"day_3\"); }\n"
+		);
+		assert_eq!(
+			fs::read_to_string(dir.path().join("notes.md")).unwrap(),
+			"# Day 3\n"
+		);
+		assert!(!dir.path().join(Path::new(super::TESTS_TEMPLATE)).exists());
+	}
+
+	#[test]
+	fn load_user_tests_template() {
+		let template_dir = tempfile::tempdir().unwrap();
+		fs::write(
+			template_dir.path().join(super::TESTS_TEMPLATE),
+			"mod tests {}\n",
+		)
+		.unwrap();
+
+		let template = Template::load(Some(template_dir.path())).unwrap();
+		assert_eq!(template.tests(), "mod tests {}\n");
+	}
+}
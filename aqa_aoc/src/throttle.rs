@@ -0,0 +1,65 @@
+//! Keeps a minimum delay between requests to the AoC servers. The time of the last request is
+//! stored in a file, so the delay holds across runs of the tool too.
+
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_DELAY: Duration = Duration::from_secs(3);
+
+pub struct Throttle {
+	/// File with the unix time of the last request, in milliseconds
+	pub path: PathBuf,
+	pub delay: Duration,
+}
+
+impl Throttle {
+	/// Sleeps until `delay` has passed since the last request, then records a new one.
+	pub fn wait(&self) -> std::io::Result<()> {
+		let last_request = fs::read_to_string(&self.path)
+			.ok()
+			.and_then(|millis| millis.trim().parse::<u64>().ok())
+			.map(Duration::from_millis);
+
+		if let Some(last_request) = last_request {
+			let elapsed = now().saturating_sub(last_request);
+			// A clock set back could make the wait arbitrarily long, never sleep more than `delay`
+			thread::sleep(self.delay.saturating_sub(elapsed).min(self.delay));
+		}
+
+		if let Some(dir) = self.path.parent() {
+			fs::create_dir_all(dir)?;
+		}
+		fs::write(&self.path, now().as_millis().to_string())
+	}
+}
+
+fn now() -> Duration {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Throttle;
+	use std::time::{Duration, Instant};
+
+	#[test]
+	fn waits_between_requests() {
+		let dir = tempfile::tempdir().unwrap();
+		let throttle = Throttle {
+			path: dir.path().join("state").join("last_request"),
+			delay: Duration::from_millis(200),
+		};
+
+		let start = Instant::now();
+		throttle.wait().unwrap();
+		assert!(start.elapsed() < Duration::from_millis(200));
+
+		throttle.wait().unwrap();
+		// The request time is stored with millisecond precision
+		assert!(start.elapsed() >= Duration::from_millis(199));
+	}
+}
@@ -0,0 +1,108 @@
+//! Waiting for a puzzle to unlock, and retrying while the servers catch up right after.
+
+use crate::date;
+use crate::error::AocError;
+use std::io::{IsTerminal, Write};
+use std::thread;
+use std::time::Duration;
+
+/// Attempts of [retry], the waits between them double starting at [FIRST_BACKOFF].
+const MAX_ATTEMPTS: u32 = 6;
+const FIRST_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Sleeps until the puzzle unlocks, with a countdown when stdout is a terminal.
+pub fn wait_for_unlock(year: u32, day: u32) {
+	let unlock_time = date::unlock_time(year, day);
+	let now = date::now_unix();
+	if now >= unlock_time {
+		return;
+	}
+
+	println!(
+		"Day {} of {} unlocks at {}",
+		day,
+		year,
+		date::format_unix(unlock_time)
+	);
+
+	let mut stdout = std::io::stdout();
+	if !stdout.is_terminal() {
+		thread::sleep(Duration::from_secs(unlock_time - now));
+		return;
+	}
+
+	loop {
+		let now = date::now_unix();
+		if now >= unlock_time {
+			break;
+		}
+		let _ = write!(
+			stdout,
+			"\rUnlocks in {}  ",
+			date::format_hms(unlock_time - now)
+		);
+		let _ = stdout.flush();
+		thread::sleep(Duration::from_secs(1));
+	}
+	println!();
+}
+
+/// Runs `f` until it succeeds, fails with an error that retrying won't fix, or runs out of
+/// attempts.
+pub fn retry<T>(mut f: impl FnMut() -> anyhow::Result<T>) -> anyhow::Result<T> {
+	let mut backoff = FIRST_BACKOFF;
+	let mut attempt = 1;
+	loop {
+		match f() {
+			Err(err) if attempt < MAX_ATTEMPTS && is_transient(&err) => {
+				eprintln!("{:#}, retrying in {}s", err, backoff.as_secs());
+				thread::sleep(backoff);
+				backoff *= 2;
+				attempt += 1;
+			}
+			result => return result,
+		}
+	}
+}
+
+/// Errors the servers give for a short while around the unlock.
+fn is_transient(err: &anyhow::Error) -> bool {
+	err.chain()
+		.find_map(|err| err.downcast_ref::<AocError>())
+		.map(|err| match err {
+			AocError::NotUnlocked { .. } | AocError::Network { .. } => true,
+			AocError::Http { status, .. } => *status >= 500,
+			_ => false,
+		})
+		.unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::error::AocError;
+
+	#[test]
+	fn retries_only_transient_errors() {
+		let mut attempts = 0;
+		let result = super::retry(|| {
+			attempts += 1;
+			Err::<(), _>(AocError::BadSession { origin: None }.into())
+		});
+		assert!(result.is_err());
+		assert_eq!(attempts, 1);
+
+		let mut attempts = 0;
+		let result = super::retry(|| {
+			attempts += 1;
+			if attempts < 2 {
+				Err(AocError::NotUnlocked {
+					url: "/2022/day/1/input".to_string(),
+				}
+				.into())
+			} else {
+				Ok(attempts)
+			}
+		});
+		assert_eq!(result.unwrap(), 2);
+	}
+}
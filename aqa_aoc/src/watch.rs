@@ -0,0 +1,165 @@
+//! Waiting for changes to the files of a day crate. Polls the modification times instead of using
+//! the notifications of the OS, it's a handful of files.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+pub const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Modification time and size of every file.
+type Snapshot = BTreeMap<PathBuf, (SystemTime, u64)>;
+
+pub struct Watcher {
+	paths: Vec<PathBuf>,
+	snapshot: Snapshot,
+}
+
+impl Watcher {
+	/// Watches the files in `paths`, and everything in the directories among them.
+	pub fn new(paths: Vec<PathBuf>) -> Watcher {
+		let snapshot = snapshot(&paths);
+		Watcher { paths, snapshot }
+	}
+
+	/// Blocks until files are changed, added or removed, and returns them. Editors often save a
+	/// file in several steps, so it waits until nothing changes for a poll before returning.
+	pub fn wait(&mut self) -> Vec<PathBuf> {
+		loop {
+			std::thread::sleep(POLL_INTERVAL);
+			let mut changed = self.changed();
+			if changed.is_empty() {
+				continue;
+			}
+
+			loop {
+				std::thread::sleep(POLL_INTERVAL);
+				let more = self.changed();
+				if more.is_empty() {
+					break;
+				}
+				changed.extend(more);
+			}
+			changed.sort();
+			changed.dedup();
+			return changed;
+		}
+	}
+
+	/// Files that differ from the last snapshot, which is replaced.
+	fn changed(&mut self) -> Vec<PathBuf> {
+		let snapshot = snapshot(&self.paths);
+		let mut changed = snapshot
+			.iter()
+			.filter(|(path, stamp)| self.snapshot.get(*path) != Some(stamp))
+			.map(|(path, _)| path.clone())
+			.collect::<Vec<_>>();
+		changed.extend(
+			self.snapshot
+				.keys()
+				.filter(|path| !snapshot.contains_key(*path))
+				.cloned(),
+		);
+		self.snapshot = snapshot;
+		changed
+	}
+}
+
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+	let mut snapshot = Snapshot::new();
+	for path in paths {
+		add_to_snapshot(path, &mut snapshot);
+	}
+	snapshot
+}
+
+/// Missing or unreadable files are left out, they show up as removed.
+fn add_to_snapshot(path: &Path, snapshot: &mut Snapshot) {
+	// Follows links, `input.txt` may link to the input cache
+	let Ok(meta) = fs::metadata(path) else {
+		return;
+	};
+
+	if meta.is_dir() {
+		let Ok(entries) = fs::read_dir(path) else {
+			return;
+		};
+		for entry in entries.filter_map(Result::ok) {
+			add_to_snapshot(&entry.path(), snapshot);
+		}
+	} else if let Ok(modified) = meta.modified() {
+		snapshot.insert(path.to_path_buf(), (modified, meta.len()));
+	}
+}
+
+/// One line per part, e.g. `part 1: 10 -> 12` or `part 2: unchanged`.
+pub fn diff_answers(
+	previous: &BTreeMap<u32, String>,
+	current: &BTreeMap<u32, String>,
+) -> Vec<String> {
+	let mut parts = previous.keys().chain(current.keys()).collect::<Vec<_>>();
+	parts.sort();
+	parts.dedup();
+
+	parts
+		.into_iter()
+		.map(|part| {
+			let change = match (previous.get(part), current.get(part)) {
+				(Some(previous), Some(current)) if previous == current => "unchanged".to_string(),
+				(Some(previous), Some(current)) => format!("{} -> {}", previous, current),
+				(None, Some(current)) => format!("new, {}", current),
+				(Some(previous), None) => format!("{} -> nothing", previous),
+				(None, None) => unreachable!("part {} has no answers", part),
+			};
+			format!("part {}: {}", part, change)
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Watcher;
+	use std::collections::BTreeMap;
+	use std::fs;
+
+	#[test]
+	fn changed() {
+		let dir = tempfile::tempdir().unwrap();
+		let src = dir.path().join("src");
+		fs::create_dir(&src).unwrap();
+		fs::write(src.join("main.rs"), "fn main() {}\n").unwrap();
+		let input = dir.path().join("input.txt");
+
+		let mut watcher = Watcher::new(vec![src.clone(), input.clone()]);
+		assert!(watcher.changed().is_empty());
+
+		fs::write(&input, "1\n").unwrap();
+		fs::write(src.join("lib.rs"), "\n").unwrap();
+		assert_eq!(watcher.changed(), vec![input.clone(), src.join("lib.rs")]);
+		assert!(watcher.changed().is_empty());
+
+		fs::write(src.join("main.rs"), "fn main() {\n}\n").unwrap();
+		fs::remove_file(&input).unwrap();
+		assert_eq!(watcher.changed(), vec![src.join("main.rs"), input]);
+	}
+
+	#[test]
+	fn diff_answers() {
+		let answers = |answers: &[(u32, &str)]| {
+			answers
+				.iter()
+				.map(|(part, answer)| (*part, answer.to_string()))
+				.collect::<BTreeMap<_, _>>()
+		};
+
+		assert_eq!(
+			super::diff_answers(&answers(&[(1, "10")]), &answers(&[(1, "10"), (2, "7")])),
+			vec!["part 1: unchanged", "part 2: new, 7"]
+		);
+		assert_eq!(
+			super::diff_answers(&answers(&[(1, "10"), (2, "7")]), &answers(&[(1, "12")])),
+			vec!["part 1: 10 -> 12", "part 2: 7 -> nothing"]
+		);
+	}
+}
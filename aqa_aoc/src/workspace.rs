@@ -0,0 +1,475 @@
+//! The cargo workspace with the day crates, and a typed view of its days: what exists on disk, what
+//! the workspace manifest says and what was recorded about the answers.
+
+use crate::answers;
+use crate::cache;
+use crate::dependencies::{self, Dependencies};
+use crate::layout::{DayCrate, Layout};
+use crate::manifest::{self, CargoTomlParserError};
+use crate::templates::{Template, Vars};
+use anyhow::{bail, Context};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use toml_edit::{DocumentMut, InlineTable, Item};
+
+/// Member with the `aoc` binary that runs any day, the days it runs are its `day_N` dependencies.
+pub const RUNNER: &str = "aoc";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayState {
+	pub day_crate: DayCrate,
+	/// The `day_N` directory exists
+	pub has_dir: bool,
+	/// The directory has a Cargo.toml
+	pub has_crate: bool,
+	pub is_member: bool,
+	pub has_input: bool,
+	/// Number of `#[test]` functions in the sources of the crate
+	pub tests: usize,
+	/// Accepted answers of both parts, from answers.toml
+	pub answers: [Option<String>; 2],
+}
+
+/// The cargo workspace in `root`, with the day crates of `year`. Changes to the manifest are only
+/// written by [Workspace::save] and [Workspace::add_day].
+pub struct Workspace {
+	root: PathBuf,
+	manifest: DocumentMut,
+	pub layout: Layout,
+	pub year: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Day {
+	NewDay(u32),
+	CreatedBefore(u32),
+}
+
+impl Day {
+	pub fn get_day(&self) -> u32 {
+		match self {
+			Day::NewDay(day) | Day::CreatedBefore(day) => *day,
+		}
+	}
+}
+
+/// Passed and failed tests of a `cargo test` run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestRun {
+	pub passed: usize,
+	pub failed: usize,
+}
+
+impl Workspace {
+	pub fn open(root: &Path, layout: Layout, year: u32) -> anyhow::Result<Workspace> {
+		Ok(Workspace {
+			root: root.to_path_buf(),
+			manifest: manifest::read(&root.join("Cargo.toml"))?,
+			layout,
+			year,
+		})
+	}
+
+	pub fn root(&self) -> &Path {
+		&self.root
+	}
+
+	pub fn manifest(&self) -> &DocumentMut {
+		&self.manifest
+	}
+
+	pub fn manifest_mut(&mut self) -> &mut DocumentMut {
+		&mut self.manifest
+	}
+
+	/// Writes the workspace manifest.
+	pub fn save(&self) -> anyhow::Result<()> {
+		manifest::write(&self.root.join("Cargo.toml"), &self.manifest)
+	}
+
+	pub fn day_crate(&self, day: u32) -> DayCrate {
+		self.layout.day_crate(self.year, day)
+	}
+
+	pub fn members(&self) -> anyhow::Result<Vec<&str>> {
+		Ok(manifest::workspace_members(&self.manifest)?
+			.iter()
+			.filter_map(toml_edit::Value::as_str)
+			.collect())
+	}
+
+	/// Days with a directory or a workspace member, in numeric order.
+	pub fn days(&self) -> anyhow::Result<Vec<DayState>> {
+		let members = self.members()?;
+
+		let mut days = self.layout.days(&self.root, self.year)?;
+		days.extend(
+			members
+				.iter()
+				.filter_map(|member| self.layout.member_day(self.year, member)),
+		);
+		days.sort_unstable();
+		days.dedup();
+
+		days.into_iter()
+			.map(|day| {
+				let day_crate = self.day_crate(day);
+				let dir = self.root.join(day_crate.dir());
+				let ledger = answers::Ledger::load(&dir)?;
+
+				Ok(DayState {
+					day_crate,
+					has_dir: dir.is_dir(),
+					has_crate: dir.join("Cargo.toml").is_file(),
+					is_member: members.contains(&day_crate.member().as_str()),
+					has_input: cache::input_path(&dir).exists(),
+					tests: count_tests(&dir.join("src"))?,
+					answers: [ledger.part_1.answer, ledger.part_2.answer],
+				})
+			})
+			.collect()
+	}
+
+	/// The highest day that is a workspace member.
+	pub fn last_member_day(&self) -> anyhow::Result<Option<u32>> {
+		Ok(self
+			.members()?
+			.into_iter()
+			.filter_map(|member| self.layout.member_day(self.year, member))
+			.max())
+	}
+
+	/// `specific_day`, or the day the workspace is at: the last day crate without an input, or the
+	/// day after the last member.
+	pub fn pick_day(&self, specific_day: Option<u32>) -> anyhow::Result<Day> {
+		if let Some(day) = specific_day {
+			return Ok(if self.root.join(self.day_crate(day).dir()).is_dir() {
+				Day::CreatedBefore(day)
+			} else {
+				Day::NewDay(day)
+			});
+		}
+
+		let last_day_without_input = self
+			.days()?
+			.into_iter()
+			.rev()
+			.find(|day| day.has_dir && !day.has_input);
+
+		Ok(match last_day_without_input {
+			Some(day) => Day::CreatedBefore(day.day_crate.day),
+			None => match self.last_member_day()?.unwrap_or(0) + 1 {
+				day @ 1..=25 => Day::NewDay(day),
+				_ => bail!("all 25 days exist, pass --day"),
+			},
+		})
+	}
+
+	/// Adds `member` to the workspace members, in day order. `false` when it already is a member.
+	pub fn add_member(&mut self, member: &str) -> anyhow::Result<bool> {
+		let members = manifest::workspace_members_mut(&mut self.manifest)?;
+		Ok(manifest::add_workspace_member(members, member))
+	}
+
+	/// `false` when `member` isn't a member.
+	pub fn remove_member(&mut self, member: &str) -> anyhow::Result<bool> {
+		let members = manifest::workspace_members_mut(&mut self.manifest)?;
+		Ok(manifest::remove_workspace_member(members, member))
+	}
+
+	/// Adds the crate of `day` to the workspace members, then creates it from `template` with
+	/// `dependencies`. Returns the dependencies that were added to `[workspace.dependencies]`.
+	pub fn add_day(
+		&mut self,
+		day: u32,
+		template: &Template,
+		dependencies: &Dependencies,
+	) -> anyhow::Result<Vec<String>> {
+		let day_crate = self.day_crate(day);
+
+		self.add_member(&day_crate.member())?;
+		let added = dependencies::add_to_workspace(&mut self.manifest, dependencies)?;
+		self.save()?;
+
+		let output = Command::new("cargo")
+			.current_dir(&self.root)
+			.args(["new", &day_crate.member(), "--name", &day_crate.name()])
+			.output()
+			.context("failed to run cargo new")?;
+		if !output.status.success() {
+			bail!(
+				"cargo new failed with code {}: {}",
+				output.status.code().unwrap_or_default(),
+				String::from_utf8_lossy(&output.stderr).trim()
+			);
+		}
+
+		let crate_dir = self.root.join(day_crate.dir());
+		template.render_into(&crate_dir, &Vars::new(&day_crate))?;
+		add_dependencies(&crate_dir, &day_crate, dependencies)?;
+		self.register_day(&day_crate)?;
+
+		Ok(added)
+	}
+
+	/// Adds the day crate to the dependencies of the [RUNNER], when the workspace has one. Only a
+	/// crate with a `lib.rs`, which exports its `Day`, and only in the flat layout, the one the
+	/// runner reads inputs from. `false` when there was nothing to add.
+	pub fn register_day(&self, day_crate: &DayCrate) -> anyhow::Result<bool> {
+		let runner_toml_path = self.root.join(RUNNER).join("Cargo.toml");
+		let is_library = self
+			.root
+			.join(day_crate.dir())
+			.join("src")
+			.join("lib.rs")
+			.is_file();
+		if self.layout != Layout::Flat || !runner_toml_path.is_file() || !is_library {
+			return Ok(false);
+		}
+
+		let mut runner_toml = manifest::read(&runner_toml_path)?;
+		let runner_dependencies = runner_dependencies(&mut runner_toml)?;
+		if runner_dependencies.contains_key(&day_crate.name()) {
+			return Ok(false);
+		}
+
+		let mut entry = InlineTable::new();
+		entry.insert("path", format!("../{}", day_crate.member()).into());
+		runner_dependencies.insert(&day_crate.name(), Item::Value(entry.into()));
+		manifest::write(&runner_toml_path, &runner_toml)?;
+		Ok(true)
+	}
+
+	/// Removes the day crate from the dependencies of the [RUNNER], see [Workspace::register_day].
+	/// `false` when it wasn't one of them.
+	pub fn unregister_day(&self, day_crate: &DayCrate) -> anyhow::Result<bool> {
+		let runner_toml_path = self.root.join(RUNNER).join("Cargo.toml");
+		if !runner_toml_path.is_file() {
+			return Ok(false);
+		}
+
+		let mut runner_toml = manifest::read(&runner_toml_path)?;
+		if runner_dependencies(&mut runner_toml)?
+			.remove(&day_crate.name())
+			.is_none()
+		{
+			return Ok(false);
+		}
+		manifest::write(&runner_toml_path, &runner_toml)?;
+		Ok(true)
+	}
+}
+
+fn runner_dependencies(
+	runner_toml: &mut DocumentMut,
+) -> Result<&mut dyn toml_edit::TableLike, CargoTomlParserError> {
+	runner_toml
+		.get_mut("dependencies")
+		.and_then(Item::as_table_like_mut)
+		.ok_or(CargoTomlParserError::FailedToGetDependencies)
+}
+
+fn add_dependencies(
+	crate_dir: &Path,
+	day_crate: &DayCrate,
+	dependencies: &Dependencies,
+) -> anyhow::Result<()> {
+	let cargo_toml_path = crate_dir.join("Cargo.toml");
+	let mut cargo_toml = manifest::read(&cargo_toml_path)?;
+
+	let cargo_dependencies = cargo_toml
+		.get_mut("dependencies")
+		.and_then(toml_edit::Item::as_table_like_mut)
+		.ok_or(CargoTomlParserError::FailedToGetDependencies)?;
+
+	for (name, dependency) in dependencies {
+		cargo_dependencies.insert(name, dependencies::crate_entry(dependency, day_crate));
+	}
+
+	manifest::write(&cargo_toml_path, &cargo_toml)
+}
+
+/// `#[test]` functions in the `.rs` files under `dir`.
+fn count_tests(dir: &Path) -> anyhow::Result<usize> {
+	if !dir.is_dir() {
+		return Ok(0);
+	}
+
+	let mut tests = 0;
+	for entry in fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))? {
+		let path = entry?.path();
+		if path.is_dir() {
+			tests += count_tests(&path)?;
+		} else if path.extension().map(|ext| ext == "rs").unwrap_or_default() {
+			tests += fs::read_to_string(&path)
+				.with_context(|| format!("failed to read {}", path.display()))?
+				.lines()
+				.filter(|line| line.trim() == "#[test]")
+				.count();
+		}
+	}
+	Ok(tests)
+}
+
+/// Runs the tests of the crate, `None` when they don't build.
+pub fn run_tests(day_crate: &DayCrate) -> anyhow::Result<Option<TestRun>> {
+	let output = Command::new("cargo")
+		.args(["test", "--quiet", "--no-fail-fast", "-p", &day_crate.name()])
+		.output()
+		.context("failed to run cargo test")?;
+
+	Ok(parse_test_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Adds up the `test result:` lines of every test binary.
+fn parse_test_output(output: &str) -> Option<TestRun> {
+	let mut run = None;
+	for line in output.lines() {
+		let Some(summary) = line.strip_prefix("test result: ") else {
+			continue;
+		};
+		let count = |what: &str| {
+			summary
+				.split(['.', ';'])
+				.find_map(|part| part.trim().strip_suffix(what))
+				.and_then(|count| count.trim().parse::<usize>().ok())
+				.unwrap_or_default()
+		};
+
+		let run = run.get_or_insert(TestRun {
+			passed: 0,
+			failed: 0,
+		});
+		run.passed += count("passed");
+		run.failed += count("failed");
+	}
+	run
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Day, TestRun, Workspace};
+	use crate::layout::Layout;
+	use std::fs;
+
+	#[test]
+	fn days() {
+		let root = tempfile::tempdir().unwrap();
+		let write = |path: &str, contents: &str| {
+			let path = root.path().join(path);
+			fs::create_dir_all(path.parent().unwrap()).unwrap();
+			fs::write(path, contents).unwrap();
+		};
+		write(
+			"Cargo.toml",
+			"[workspace]\nmembers = ['aqa_aoc', 'day_1', 'day_2']\n",
+		);
+		write("day_1/Cargo.toml", "");
+		write("day_1/input.txt", "1\n");
+		write(
+			"day_1/src/main.rs",
+			"#[cfg(test)]\nmod tests {\n\t#[test]\n\tfn a() {}\n\n\t#[test]\n\tfn b() {}\n}\n",
+		);
+		write("day_1/answers.toml", "[part_1]\nanswer = \"24000\"\n");
+		write("day_3/Cargo.toml", "");
+
+		let workspace = Workspace::open(root.path(), Layout::Flat, 2022).unwrap();
+		let days = workspace.days().unwrap();
+
+		let summary = days
+			.iter()
+			.map(|day| {
+				(
+					day.day_crate.day,
+					day.has_crate,
+					day.is_member,
+					day.has_input,
+					day.tests,
+				)
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(
+			summary,
+			vec![
+				(1, true, true, true, 2),
+				(2, false, true, false, 0),
+				(3, true, false, false, 0)
+			]
+		);
+		assert_eq!(days[0].answers, [Some("24000".to_string()), None]);
+		assert_eq!(workspace.last_member_day().unwrap(), Some(2));
+
+		assert_eq!(workspace.pick_day(None).unwrap(), Day::CreatedBefore(3));
+		assert_eq!(workspace.pick_day(Some(1)).unwrap(), Day::CreatedBefore(1));
+		assert_eq!(workspace.pick_day(Some(9)).unwrap(), Day::NewDay(9));
+
+		write(
+			"Cargo.toml",
+			"[workspace]\nmembers = ['aqa_aoc', 'day_1', 'day_25']\n",
+		);
+		write("day_25/Cargo.toml", "");
+		write("day_25/input.txt", "1\n");
+		let workspace = Workspace::open(root.path(), Layout::Flat, 2022).unwrap();
+		assert_eq!(workspace.pick_day(None).unwrap(), Day::CreatedBefore(3));
+		fs::remove_dir_all(root.path().join("day_3")).unwrap();
+		assert!(workspace.pick_day(None).is_err());
+	}
+
+	#[test]
+	fn register_day() {
+		let root = tempfile::tempdir().unwrap();
+		let write = |path: &str, contents: &str| {
+			let path = root.path().join(path);
+			fs::create_dir_all(path.parent().unwrap()).unwrap();
+			fs::write(path, contents).unwrap();
+		};
+		let runner_toml = "[package]\nname = \"aoc\"\n\n[dependencies]\naoc_common = { path = \"../aoc_common\" }\n";
+		write(
+			"Cargo.toml",
+			"[workspace]\nmembers = ['aoc', 'day_1', 'day_2']\n",
+		);
+		write("aoc/Cargo.toml", runner_toml);
+		write("day_1/src/lib.rs", "");
+		write("day_2/src/main.rs", "");
+
+		let workspace = Workspace::open(root.path(), Layout::Flat, 2022).unwrap();
+		let day_1 = workspace.day_crate(1);
+		assert!(workspace.register_day(&day_1).unwrap());
+		assert!(!workspace.register_day(&day_1).unwrap());
+		// Not a library, the runner can't use it
+		assert!(!workspace.register_day(&workspace.day_crate(2)).unwrap());
+		assert_eq!(
+			fs::read_to_string(root.path().join("aoc/Cargo.toml")).unwrap(),
+			format!("{}day_1 = {{ path = \"../day_1\" }}\n", runner_toml)
+		);
+
+		assert!(workspace.unregister_day(&day_1).unwrap());
+		assert!(!workspace.unregister_day(&day_1).unwrap());
+		assert_eq!(
+			fs::read_to_string(root.path().join("aoc/Cargo.toml")).unwrap(),
+			runner_toml
+		);
+	}
+
+	#[test]
+	fn parse_test_output() {
+		let output = "
+running 2 tests
+..
+test result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+running 1 test
+F
+test result: FAILED. 0 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+		assert_eq!(
+			super::parse_test_output(output),
+			Some(TestRun {
+				passed: 2,
+				failed: 1
+			})
+		);
+		assert_eq!(super::parse_test_output("error: could not compile"), None);
+	}
+}
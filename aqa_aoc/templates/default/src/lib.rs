@@ -0,0 +1,37 @@
+//! Advent of Code {{year}}, day {{day}}: https://adventofcode.com/{{year}}/day/{{day}}
+
+use aoc_common::Solution;
+use std::fmt::Display;
+
+pub struct Day;
+
+impl Solution for Day {
+	const DAY: u32 = {{day}};
+
+	type Input = Vec<String>;
+
+	fn parse(input: &str) -> anyhow::Result<Vec<String>> {
+		parse_input(input)
+	}
+
+	fn part_1(input: &Vec<String>) -> anyhow::Result<impl Display> {
+		part_1(input)
+	}
+
+	fn part_2(input: &Vec<String>) -> anyhow::Result<impl Display> {
+		part_2(input)
+	}
+}
+
+/// The lines of the input, until they get a type of their own.
+fn parse_input(input: &str) -> anyhow::Result<Vec<String>> {
+	Ok(input.lines().map(str::to_string).collect())
+}
+
+fn part_1(_input: &[String]) -> anyhow::Result<u64> {
+	todo!()
+}
+
+fn part_2(_input: &[String]) -> anyhow::Result<u64> {
+	todo!()
+}
@@ -0,0 +1,3 @@
+fn main() -> anyhow::Result<()> {
+	aoc_common::main::<{{crate_name}}::Day>(env!("CARGO_MANIFEST_DIR"))
+}
@@ -0,0 +1,12 @@
+#[cfg(test)]
+mod tests {
+	const EXAMPLE: &str = {{example}};
+
+	#[test]
+	fn part_1_example() {
+{{part_1_assertion}}	}
+
+	#[test]
+	fn part_2_example() {
+{{part_2_assertion}}	}
+}
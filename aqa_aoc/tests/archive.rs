@@ -0,0 +1,105 @@
+mod common;
+
+use common::{assert_success, TestWorkspace};
+
+#[test]
+fn archive_and_restore() {
+	let workspace = TestWorkspace::new(&["day_1", "day_2"]);
+	workspace.write_cfg("");
+	workspace.add_day_crate(1);
+	workspace.write(
+		"day_1/src/main.rs",
+		"fn main() {\n\tprintln!(\"Part 1: 24000\");\n\tprintln!(\"Time 2: 1500000ns\");\n\tprintln!(\"Part 2: 45000\");\n}\n",
+	);
+	workspace.write("day_1/input.txt", "abc");
+	workspace.write("day_1/answers.toml", "[part_1]\nanswer = \"24000\"\n");
+	workspace.add_day_crate(2);
+	// The runner of the days, day_1 is a library it can run
+	workspace.write("day_1/src/lib.rs", "");
+	let runner_toml = "[package]\nname = \"aoc\"\n\n[dependencies]\n";
+	workspace.write(
+		"aoc/Cargo.toml",
+		&format!(
+			"{}day_1 = {{ path = \"../day_1\" }}\nday_2 = {{ path = \"../day_2\" }}\n",
+			runner_toml
+		),
+	);
+
+	let output = workspace.run(&["archive", "--to", "aoc_2022", "--remove"]);
+	assert_success(&output);
+
+	let manifest: toml::Value = toml::from_str(&workspace.read("aoc_2022/archive.toml")).unwrap();
+	assert_eq!(manifest["year"].as_integer(), Some(2022));
+	assert_eq!(manifest["layout"].as_str(), Some("flat"));
+	let days = manifest["day"].as_array().unwrap();
+	assert_eq!(days.len(), 2);
+	assert_eq!(days[0]["member"].as_str(), Some("day_1"));
+	assert_eq!(
+		days[0]["input_sha256"].as_str(),
+		Some("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+	);
+	assert_eq!(days[0]["part_1"]["answer"].as_str(), Some("24000"));
+	assert_eq!(days[0]["part_2"]["printed"].as_str(), Some("45000"));
+	assert_eq!(days[0]["part_2"]["time_us"].as_integer(), Some(1500));
+	// Reported by the solution only
+	assert!(days[0]["part_1"].get("time_us").is_none());
+	// Prints no parts and has no input, it's archived all the same
+	assert_eq!(days[1]["member"].as_str(), Some("day_2"));
+	assert!(days[1].get("input_sha256").is_none());
+
+	assert_eq!(workspace.read("aoc_2022/day_1/input.txt"), "abc");
+	assert!(!workspace.file("aoc_2022/day_1/target").exists());
+	assert!(!workspace.file("day_1").exists());
+	assert_eq!(
+		workspace.read("Cargo.toml"),
+		"[workspace]\nmembers = [\n]\n"
+	);
+	assert_eq!(workspace.read("aoc/Cargo.toml"), runner_toml);
+
+	let output = workspace.run(&["restore", "aoc_2022"]);
+	assert_success(&output);
+	assert!(
+		String::from_utf8_lossy(&output.stdout).contains("Restored 2 of 2 day crates of 2022\n")
+	);
+	let cargo_toml: toml::Value = toml::from_str(&workspace.read("Cargo.toml")).unwrap();
+	assert_eq!(
+		cargo_toml["workspace"]["members"],
+		toml::Value::from(vec!["day_1", "day_2"])
+	);
+	assert_eq!(workspace.read("day_1/input.txt"), "abc");
+	assert_eq!(
+		workspace.read("aoc/Cargo.toml"),
+		format!("{}day_1 = {{ path = \"../day_1\" }}\n", runner_toml)
+	);
+	assert_eq!(
+		workspace.read("day_1/answers.toml"),
+		"[part_1]\nanswer = \"24000\"\n"
+	);
+
+	// Existing crates are left alone
+	let output = workspace.run(&["restore", "aoc_2022"]);
+	assert_success(&output);
+	assert!(String::from_utf8_lossy(&output.stdout)
+		.contains("day_1 already exists, not restoring it\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn failed_archive_leaves_no_bundle() {
+	let workspace = TestWorkspace::new(&["day_1"]);
+	workspace.write_cfg("");
+	workspace.add_day_crate(1);
+	workspace.write("day_1/input.txt", "abc");
+	// Can't be copied, so the archive fails after starting the bundle
+	std::os::unix::fs::symlink("missing.txt", workspace.file("day_1/broken.txt")).unwrap();
+
+	let output = workspace.run(&["archive", "--to", "aoc_2022"]);
+	assert!(!output.status.success());
+	assert!(!workspace.file("aoc_2022").exists());
+	assert!(!workspace.file(".aoc_2022.partial").exists());
+	assert!(workspace.file("day_1").exists());
+
+	std::fs::remove_file(workspace.file("day_1/broken.txt")).unwrap();
+	assert_success(&workspace.run(&["archive", "--to", "aoc_2022"]));
+	assert_eq!(workspace.read("aoc_2022/day_1/input.txt"), "abc");
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use common::{assert_success, TestWorkspace};
+
+#[test]
+fn help_works_without_config() {
+	let workspace = TestWorkspace::new(&[]);
+
+	let output = workspace.run(&["--help"]);
+	assert_success(&output);
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	assert!(stdout.contains("Commands:"));
+	assert!(stdout.contains("fetch"));
+
+	let output = workspace.run(&["help", "submit"]);
+	assert_success(&output);
+	assert!(
+		String::from_utf8_lossy(&output.stdout).contains("Usage: aqa_aoc submit --day N --part P")
+	);
+}
+
+#[test]
+fn rejects_unknown_flags_and_invalid_days() {
+	let workspace = TestWorkspace::new(&[]);
+	workspace.write_cfg("");
+
+	let output = workspace.run(&["fetch", "--dya=1"]);
+	assert!(!output.status.success());
+	assert!(String::from_utf8_lossy(&output.stderr).contains("unknown flag --dya for `fetch`"));
+
+	let output = workspace.run(&["new", "--day=26"]);
+	assert!(!output.status.success());
+	assert!(String::from_utf8_lossy(&output.stderr).contains("day must be between 1 and 25"));
+
+	let output = workspace.run(&["frobnicate"]);
+	assert!(!output.status.success());
+	assert!(String::from_utf8_lossy(&output.stderr).contains("unknown command `frobnicate`"));
+}
+
+#[test]
+fn new_works_offline() {
+	let workspace = TestWorkspace::new(&[]);
+	workspace.write_cfg("base_url = \"http://127.0.0.1:1\"\n");
+
+	assert_success(&workspace.run(&["new", "--day", "3"]));
+
+	assert!(workspace.read("Cargo.toml").contains("day_3"));
+	assert!(workspace.file("day_3/src/main.rs").exists());
+	// Without an aoc_common in the workspace, the crate doesn't depend on it
+	let day_cargo_toml: toml::Value = toml::from_str(&workspace.read("day_3/Cargo.toml")).unwrap();
+	assert!(day_cargo_toml["dependencies"].get("anyhow").is_some());
+	assert!(day_cargo_toml["dependencies"].get("aoc_common").is_none());
+	assert!(!workspace.file("day_3/input.txt").exists());
+}
+
+#[test]
+fn new_uses_configured_dependencies() {
+	let workspace = TestWorkspace::new(&[]);
+	workspace.write_cfg(
+		"[dependencies]
+rayon = { version = \"1.6\", features = [\"web_spin_lock\"] }
+aoc_common = { path = \"aoc_common\" }
+itertools = { workspace = true, version = \"0.10\" }
+",
+	);
+
+	assert_success(&workspace.run(&["new", "--day", "1"]));
+
+	let day_cargo_toml: toml::Value = toml::from_str(&workspace.read("day_1/Cargo.toml")).unwrap();
+	let dependencies = day_cargo_toml["dependencies"].as_table().unwrap();
+	assert_eq!(
+		dependencies.keys().collect::<Vec<_>>(),
+		vec!["aoc_common", "itertools", "rayon"]
+	);
+	assert_eq!(
+		dependencies["aoc_common"]["path"].as_str(),
+		Some("../aoc_common")
+	);
+	assert_eq!(dependencies["itertools"]["workspace"].as_bool(), Some(true));
+	assert_eq!(
+		dependencies["rayon"]["features"][0].as_str(),
+		Some("web_spin_lock")
+	);
+
+	let cargo_toml: toml::Value = toml::from_str(&workspace.read("Cargo.toml")).unwrap();
+	assert_eq!(
+		cargo_toml["workspace"]["dependencies"]["itertools"].as_str(),
+		Some("0.10")
+	);
+}
+
+#[test]
+fn fetch_requires_existing_crate() {
+	let workspace = TestWorkspace::new(&[]);
+	workspace.write_cfg("base_url = \"http://127.0.0.1:1\"\n");
+
+	let output = workspace.run(&["fetch", "--day", "9"]);
+	assert!(!output.status.success());
+	assert!(String::from_utf8_lossy(&output.stderr).contains("day_9 doesn't exist"));
+	assert!(!workspace.read("Cargo.toml").contains("day_9"));
+}
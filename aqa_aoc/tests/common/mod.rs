@@ -0,0 +1,236 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::{Arc, Mutex};
+
+pub const SESSION_COOKIE: &str = "53616c7465645f5f";
+
+type Routes = HashMap<(String, String), (u16, String)>;
+
+#[derive(Debug, Clone)]
+pub struct Request {
+	pub method: String,
+	pub path: String,
+	pub headers: Vec<(String, String)>,
+	pub body: String,
+}
+
+impl Request {
+	pub fn header(&self, name: &str) -> Option<&str> {
+		self.headers
+			.iter()
+			.find(|(header, _)| header.eq_ignore_ascii_case(name))
+			.map(|(_, value)| value.as_str())
+	}
+}
+
+/// Local stand-in for adventofcode.com. Answers with canned responses and records every request.
+pub struct StandIn {
+	pub addr: String,
+	routes: Arc<Mutex<Routes>>,
+	requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl StandIn {
+	pub fn start() -> StandIn {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let addr = format!("http://{}", listener.local_addr().unwrap());
+
+		let routes = Arc::new(Mutex::new(HashMap::new()));
+		let requests = Arc::new(Mutex::new(Vec::new()));
+
+		{
+			let routes = Arc::clone(&routes);
+			let requests = Arc::clone(&requests);
+			std::thread::spawn(move || {
+				for stream in listener.incoming().filter_map(Result::ok) {
+					handle_connection(stream, &routes, &requests);
+				}
+			});
+		}
+
+		StandIn {
+			addr,
+			routes,
+			requests,
+		}
+	}
+
+	pub fn route(&self, method: &str, path: &str, status: u16, body: impl Into<String>) {
+		self.routes.lock().unwrap().insert(
+			(method.to_string(), path.to_string()),
+			(status, body.into()),
+		);
+	}
+
+	/// Serves the calendar of `year`, showing `user` as logged in or nobody for an expired session.
+	pub fn route_calendar(&self, year: u32, user: Option<&str>) {
+		let header = match user {
+			Some(user) => format!(
+				"<div class=\"user\">{} <span class=\"star-count\">2*</span></div>",
+				user
+			),
+			None => "<ul><li><a href=\"/auth/login\">[Log In]</a></li></ul>".to_string(),
+		};
+		self.route(
+			"GET",
+			&format!("/{}", year),
+			200,
+			format!(
+				"<html><body><header><div>{}</div></header><main></main></body></html>",
+				header
+			),
+		);
+	}
+
+	pub fn requests(&self) -> Vec<Request> {
+		self.requests.lock().unwrap().clone()
+	}
+}
+
+fn handle_connection(stream: TcpStream, routes: &Mutex<Routes>, requests: &Mutex<Vec<Request>>) {
+	let mut reader = BufReader::new(stream);
+
+	let mut request_line = String::new();
+	if reader.read_line(&mut request_line).is_err() {
+		return;
+	}
+	let mut request_line = request_line.split_whitespace();
+	let method = request_line.next().unwrap_or_default().to_string();
+	let path = request_line.next().unwrap_or_default().to_string();
+
+	let mut headers = Vec::new();
+	loop {
+		let mut line = String::new();
+		if reader.read_line(&mut line).is_err() || line == "\r\n" || line.is_empty() {
+			break;
+		}
+		if let Some((name, value)) = line.split_once(':') {
+			headers.push((name.trim().to_string(), value.trim().to_string()));
+		}
+	}
+
+	let content_length = headers
+		.iter()
+		.find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+		.and_then(|(_, value)| value.parse().ok())
+		.unwrap_or(0);
+	let mut body = vec![0; content_length];
+	let _ = reader.read_exact(&mut body);
+
+	let (status, response) = routes
+		.lock()
+		.unwrap()
+		.get(&(method.clone(), path.clone()))
+		.cloned()
+		.unwrap_or_else(|| (404, "404 Not Found".to_string()));
+
+	requests.lock().unwrap().push(Request {
+		method,
+		path,
+		headers,
+		body: String::from_utf8_lossy(&body).into_owned(),
+	});
+
+	let _ = write!(
+		reader.get_mut(),
+		"HTTP/1.1 {} Stand-in\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+		status,
+		response.len(),
+		response
+	);
+}
+
+/// Throwaway AoC workspace in a temp directory.
+pub struct TestWorkspace {
+	dir: tempfile::TempDir,
+}
+
+impl TestWorkspace {
+	pub fn new(members: &[&str]) -> TestWorkspace {
+		let dir = tempfile::tempdir().unwrap();
+
+		let members = members
+			.iter()
+			.map(|member| format!("    '{}',\n", member))
+			.collect::<String>();
+		fs::write(
+			dir.path().join("Cargo.toml"),
+			format!("[workspace]\nmembers = [\n{}]\n", members),
+		)
+		.unwrap();
+
+		TestWorkspace { dir }
+	}
+
+	pub fn path(&self) -> &Path {
+		self.dir.path()
+	}
+
+	pub fn file(&self, path: impl AsRef<Path>) -> PathBuf {
+		self.dir.path().join(path)
+	}
+
+	pub fn read(&self, path: impl AsRef<Path>) -> String {
+		fs::read_to_string(self.file(path)).unwrap()
+	}
+
+	pub fn write(&self, path: impl AsRef<Path>, contents: &str) {
+		let path = self.file(path);
+		fs::create_dir_all(path.parent().unwrap()).unwrap();
+		fs::write(path, contents).unwrap();
+	}
+
+	pub fn write_cfg(&self, extra: &str) {
+		self.write(
+			"aoc_cfg.toml",
+			&format!(
+				"year = 2022\nsession_cookie = \"{}\"\nrequest_delay_ms = 0\n{}",
+				SESSION_COOKIE, extra
+			),
+		);
+	}
+
+	pub fn add_day_crate(&self, day: u32) {
+		self.write(
+			format!("day_{}/Cargo.toml", day),
+			&format!(
+				"[package]\nname = \"day_{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n",
+				day
+			),
+		);
+		self.write(
+			format!("day_{}/src/main.rs", day),
+			"fn main() {\n\tprintln!(\"Hello, world!\");\n}\n",
+		);
+	}
+
+	pub fn command(&self) -> Command {
+		let mut command = Command::new(env!("CARGO_BIN_EXE_aqa_aoc"));
+		command
+			.current_dir(self.path())
+			.env_remove("AQA_AOC_BASE_URL")
+			.env_remove("AQA_AOC_SESSION")
+			.env("XDG_CONFIG_HOME", self.file(".config"));
+		command
+	}
+
+	pub fn run(&self, args: &[&str]) -> Output {
+		self.command().args(args).output().unwrap()
+	}
+}
+
+pub fn assert_success(output: &Output) {
+	assert!(
+		output.status.success(),
+		"status: {}\nstdout:\n{}\nstderr:\n{}",
+		output.status,
+		String::from_utf8_lossy(&output.stdout),
+		String::from_utf8_lossy(&output.stderr),
+	);
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::{assert_success, StandIn, TestWorkspace};
+
+#[test]
+fn reports_and_fixes_workspace_problems() {
+	let server = StandIn::start();
+	server.route_calendar(2022, Some("tester"));
+	server.route("GET", "/2022/day/2/input", 200, "2\n");
+
+	let workspace = TestWorkspace::new(&["aoc", "day_3", "day_1", "day_16"]);
+	workspace.write_cfg(&format!("base_url = \"{}\"\n", server.addr));
+	for day in 1..=3 {
+		workspace.add_day_crate(day);
+	}
+	workspace.write("day_1/input.txt", "1\n");
+	workspace.write("day_3/input.txt", "3\n");
+	workspace.write("day_2/src/lib.rs", "pub struct Day;\n");
+	let runner_toml = "[package]\nname = \"aoc\"\n\n[dependencies]\n";
+	workspace.write("aoc/Cargo.toml", runner_toml);
+
+	let output = workspace.run(&["doctor"]);
+	assert!(!output.status.success());
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	assert!(stdout.contains("workspace member \"day_16\" has no directory"));
+	assert!(stdout.contains("crate \"day_2\" is not a workspace member"));
+	assert!(stdout.contains("day_2 has no input.txt"));
+	assert!(stdout.contains("day members are not in numeric order"));
+	assert!(server.requests().is_empty());
+
+	assert_success(&workspace.run(&["doctor", "--fix"]));
+	assert_eq!(
+		workspace.read("Cargo.toml"),
+		"[workspace]\nmembers = [\n    'aoc',\n    'day_1',\n    'day_2',\n    'day_3',\n]\n"
+	);
+	assert_eq!(workspace.read("day_2/input.txt"), "2\n");
+	// day_2 became a member, so the runner gets it too
+	assert_eq!(
+		workspace.read("aoc/Cargo.toml"),
+		format!("{}day_2 = {{ path = \"../day_2\" }}\n", runner_toml)
+	);
+
+	let output = workspace.run(&["doctor"]);
+	assert_success(&output);
+	assert!(String::from_utf8_lossy(&output.stdout).contains("No problems found"));
+}
+
+#[test]
+fn doctor_works_without_config() {
+	let workspace = TestWorkspace::new(&["day_1"]);
+	workspace.add_day_crate(1);
+	workspace.write("day_1/input.txt", "1\n");
+
+	assert_success(&workspace.run(&["doctor"]));
+}
@@ -0,0 +1,409 @@
+mod common;
+
+use common::{assert_success, StandIn, TestWorkspace, SESSION_COOKIE};
+
+const INPUT: &str = "1000\n2000\n3000\n\n4000\n";
+
+#[test]
+fn creates_next_day_and_downloads_input() {
+	let server = StandIn::start();
+	server.route_calendar(2022, Some("tester"));
+	server.route("GET", "/2022/day/2/input", 200, INPUT);
+
+	let workspace = TestWorkspace::new(&["aqa_aoc", "day_1"]);
+	workspace.write_cfg(&format!("base_url = \"{}\"\n", server.addr));
+	workspace.write(
+		"aoc_common/Cargo.toml",
+		"[package]\nname = \"aoc_common\"\n",
+	);
+	workspace.add_day_crate(1);
+	workspace.write("day_1/input.txt", "input");
+
+	let output = workspace.run(&[]);
+	assert_success(&output);
+
+	assert_eq!(
+		workspace.read("Cargo.toml"),
+		"[workspace]\nmembers = [\n    'aqa_aoc',\n    'day_1',\n    'day_2',\n]\n"
+	);
+
+	let day_cargo_toml: toml::Value = toml::from_str(&workspace.read("day_2/Cargo.toml")).unwrap();
+	assert_eq!(day_cargo_toml["package"]["name"].as_str(), Some("day_2"));
+	let dependencies = day_cargo_toml["dependencies"].as_table().unwrap();
+	for dependency in ["anyhow", "itertools", "regex", "nom", "aoc_common"] {
+		assert!(
+			dependencies.contains_key(dependency),
+			"missing {}",
+			dependency
+		);
+	}
+
+	assert_eq!(
+		dependencies["aoc_common"]["path"].as_str(),
+		Some("../aoc_common")
+	);
+	assert!(workspace
+		.read("day_2/src/main.rs")
+		.contains("aoc_common::main::<day_2::Day>(env!(\"CARGO_MANIFEST_DIR\"))"));
+	assert!(workspace
+		.read("day_2/src/lib.rs")
+		.contains("impl Solution for Day {"));
+
+	assert_eq!(workspace.read("day_2/input.txt"), INPUT);
+
+	// The input request itself tells whether the session is still good
+	let requests = server.requests();
+	assert_eq!(requests[0].method, "GET");
+	assert_eq!(requests[0].path, "/2022/day/2/input");
+	assert_eq!(
+		requests[0].header("Cookie"),
+		Some(format!("session={}", SESSION_COOKIE).as_str())
+	);
+}
+
+#[test]
+fn downloads_input_for_existing_day() {
+	let server = StandIn::start();
+	server.route_calendar(2022, Some("tester"));
+	server.route("GET", "/2022/day/1/input", 200, INPUT);
+
+	let workspace = TestWorkspace::new(&["day_1"]);
+	workspace.write_cfg(&format!("base_url = \"{}\"\n", server.addr));
+	workspace.add_day_crate(1);
+	let cargo_toml = workspace.read("Cargo.toml");
+
+	let output = workspace.run(&["--day=1"]);
+	assert_success(&output);
+
+	assert_eq!(workspace.read("day_1/input.txt"), INPUT);
+	assert_eq!(workspace.read("Cargo.toml"), cargo_toml);
+}
+
+#[cfg(unix)]
+#[test]
+fn keeps_inputs_in_the_cache() {
+	let server = StandIn::start();
+	server.route_calendar(2022, Some("tester"));
+	server.route("GET", "/2022/day/1/input", 200, INPUT);
+
+	let workspace = TestWorkspace::new(&["day_1"]);
+	workspace.write_cfg(&format!(
+		"base_url = \"{}\"\ninputs = \"cache\"\n",
+		server.addr
+	));
+	workspace.add_day_crate(1);
+	// Ignored already, not added again
+	workspace.write(".gitignore", "target/\n/.aqa_aoc/\n");
+
+	let output = workspace.run(&["fetch", "--day=1"]);
+	assert_success(&output);
+
+	assert_eq!(workspace.read(".aqa_aoc/inputs/day_1/input.txt"), INPUT);
+	assert!(workspace
+		.file("day_1/input.txt")
+		.symlink_metadata()
+		.unwrap()
+		.is_symlink());
+	assert_eq!(workspace.read("day_1/input.txt"), INPUT);
+	assert_eq!(
+		workspace.read(".gitignore"),
+		"target/\n/.aqa_aoc/\n\n# Puzzle inputs, managed by aqa_aoc\n/day_*/input.txt\n/day_*/input.meta.toml\n# End of puzzle inputs\n"
+	);
+
+	// Not downloaded again through the link
+	let output = workspace.run(&["fetch", "--day=1"]);
+	assert_success(&output);
+	assert_eq!(
+		server
+			.requests()
+			.iter()
+			.filter(|request| request.path == "/2022/day/1/input")
+			.count(),
+		1
+	);
+}
+
+#[test]
+fn downloads_input_only_once() {
+	let server = StandIn::start();
+	server.route_calendar(2022, Some("tester"));
+	server.route("GET", "/2022/day/1/input", 200, INPUT);
+
+	let workspace = TestWorkspace::new(&["day_1"]);
+	workspace.write_cfg(&format!(
+		"base_url = \"{}\"\ncontact = \"me@example.com\"\n",
+		server.addr
+	));
+	workspace.add_day_crate(1);
+
+	assert_success(&workspace.run(&["fetch", "--day=1"]));
+	let meta: toml::Value = toml::from_str(&workspace.read("day_1/input.meta.toml")).unwrap();
+	assert_eq!(meta["year"].as_integer(), Some(2022));
+	assert_eq!(meta["day"].as_integer(), Some(1));
+	assert_eq!(meta["sha256"].as_str().unwrap().len(), 64);
+	assert!(!meta["session"].as_str().unwrap().contains(SESSION_COOKIE));
+
+	let input_requests = || {
+		server
+			.requests()
+			.into_iter()
+			.filter(|request| request.path == "/2022/day/1/input")
+			.collect::<Vec<_>>()
+	};
+	assert_eq!(input_requests().len(), 1);
+	assert_eq!(
+		input_requests()[0].header("User-Agent"),
+		Some(concat!(
+			"aqa_aoc/",
+			env!("CARGO_PKG_VERSION"),
+			" (me@example.com)"
+		))
+	);
+
+	let output = workspace.run(&["fetch", "--day=1"]);
+	assert_success(&output);
+	assert!(String::from_utf8_lossy(&output.stdout).contains("not downloading it again"));
+	assert_eq!(input_requests().len(), 1);
+
+	workspace.write("day_1/input.txt", "edited");
+	assert_success(&workspace.run(&["fetch", "--day=1", "--force"]));
+	assert_eq!(input_requests().len(), 2);
+	assert_eq!(workspace.read("day_1/input.txt"), INPUT);
+
+	assert!(workspace.file(".aqa_aoc/last_request").exists());
+}
+
+#[test]
+fn env_overrides_base_url() {
+	let server = StandIn::start();
+	server.route_calendar(2022, Some("tester"));
+	server.route("GET", "/2022/day/1/input", 200, INPUT);
+
+	let workspace = TestWorkspace::new(&["day_1"]);
+	workspace.write_cfg("base_url = \"http://127.0.0.1:1\"\n");
+	workspace.add_day_crate(1);
+
+	let output = workspace
+		.command()
+		.args(["--day", "1"])
+		.env("AQA_AOC_BASE_URL", &server.addr)
+		.output()
+		.unwrap();
+	assert_success(&output);
+
+	assert_eq!(workspace.read("day_1/input.txt"), INPUT);
+}
+
+#[test]
+fn fails_when_input_is_not_available() {
+	let server = StandIn::start();
+	server.route_calendar(2022, Some("tester"));
+
+	let workspace = TestWorkspace::new(&["day_1"]);
+	workspace.write_cfg(&format!("base_url = \"{}\"\n", server.addr));
+	workspace.add_day_crate(1);
+
+	let output = workspace.run(&["--day=1"]);
+	assert_eq!(output.status.code(), Some(3));
+	assert!(String::from_utf8_lossy(&output.stderr).contains("not unlocked yet"));
+	assert!(!workspace.file("day_1/input.txt").exists());
+}
+
+#[test]
+fn exit_codes_tell_failures_apart() {
+	let server = StandIn::start();
+	server.route_calendar(2022, Some("tester"));
+	server.route(
+		"GET",
+		"/2022/day/1/input",
+		400,
+		"Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+	);
+	server.route("GET", "/2022/day/2/input", 429, "");
+	server.route("GET", "/2022/day/3/input", 503, "");
+
+	let workspace = TestWorkspace::new(&["day_1", "day_2", "day_3"]);
+	workspace.write_cfg(&format!("base_url = \"{}\"\n", server.addr));
+	for day in 1..=3 {
+		workspace.add_day_crate(day);
+	}
+
+	assert_eq!(workspace.run(&["fetch", "--day=1"]).status.code(), Some(4));
+	assert_eq!(workspace.run(&["fetch", "--day=2"]).status.code(), Some(5));
+	assert_eq!(workspace.run(&["fetch", "--day=3"]).status.code(), Some(8));
+
+	let output = workspace
+		.command()
+		.args(["fetch", "--day=1"])
+		.env("AQA_AOC_BASE_URL", "http://127.0.0.1:1")
+		.output()
+		.unwrap();
+	assert_eq!(output.status.code(), Some(6));
+}
+
+const PART_1: &str = "<article class=\"day-desc\"><h2>--- Day 1: Calorie Counting ---</h2><p>Find the Elf carrying the <em>most Calories</em>.</p></article>";
+const PART_2: &str = "<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Find the top <em>three</em>.</p></article>";
+
+#[test]
+fn saves_puzzle_description_and_appends_part_2() {
+	let server = StandIn::start();
+	server.route_calendar(2022, Some("tester"));
+	server.route("GET", "/2022/day/1/input", 200, INPUT);
+	server.route(
+		"GET",
+		"/2022/day/1",
+		200,
+		format!("<html><main>{}</main></html>", PART_1),
+	);
+
+	let workspace = TestWorkspace::new(&["day_1"]);
+	workspace.write_cfg(&format!("base_url = \"{}\"\n", server.addr));
+	workspace.add_day_crate(1);
+
+	assert_success(&workspace.run(&["--day=1"]));
+	assert_eq!(
+		workspace.read("day_1/README.md"),
+		"## --- Day 1: Calorie Counting ---\n\nFind the Elf carrying the *most Calories*.\n"
+	);
+
+	server.route(
+		"GET",
+		"/2022/day/1",
+		200,
+		format!("<html><main>{}{}</main></html>", PART_1, PART_2),
+	);
+
+	assert_success(&workspace.run(&["--day=1"]));
+	assert_eq!(
+		workspace.read("day_1/README.md"),
+		"## --- Day 1: Calorie Counting ---\n\nFind the Elf carrying the *most Calories*.\n\n\
+		 ## --- Part Two ---\n\nFind the top *three*.\n"
+	);
+}
+
+#[test]
+fn generates_example_tests() {
+	let server = StandIn::start();
+	server.route_calendar(2022, Some("tester"));
+	server.route("GET", "/2022/day/1/input", 200, INPUT);
+	server.route(
+		"GET",
+		"/2022/day/1",
+		200,
+		"<html><main><article class=\"day-desc\"><h2>--- Day 1 ---</h2>\
+		 <pre><code>not this one</code></pre>\
+		 <pre><code>1000\n2000\n</code></pre>\
+		 <p>The answer is <code><em>3000</em></code>.</p></article></main></html>",
+	);
+
+	let workspace = TestWorkspace::new(&[]);
+	workspace.write_cfg(&format!("base_url = \"{}\"\n", server.addr));
+
+	assert_success(&workspace.run(&["--day=1", "--example=2"]));
+
+	let lib_rs = workspace.read("day_1/src/lib.rs");
+	assert!(lib_rs.contains("\tconst EXAMPLE: &str = \"1000\n2000\n\";\n"));
+	assert!(lib_rs.contains(
+		"\t\tassert_eq!(super::part_1(&super::parse_input(EXAMPLE).unwrap()).unwrap(), 3000);\n"
+	));
+	assert!(lib_rs.contains("\tfn part_2_example() {\n"));
+}
+
+#[test]
+fn scaffolds_from_user_template() {
+	let server = StandIn::start();
+	server.route_calendar(2022, Some("tester"));
+	server.route("GET", "/2022/day/1/input", 200, INPUT);
+
+	let workspace = TestWorkspace::new(&[]);
+	workspace.write_cfg("template_dir = \"template\"\n");
+	workspace.write(
+		"template/src/main.rs",
+		"fn main() {\n\tprintln!(\"{{year}} {{day}} {{crate_name}}\");\n}\n",
+	);
+	workspace.write("template/src/parse.rs", "// {{input_path}}\n");
+
+	let output = workspace
+		.command()
+		.arg("--day=1")
+		.env("AQA_AOC_BASE_URL", &server.addr)
+		.output()
+		.unwrap();
+	assert_success(&output);
+
+	assert_eq!(
+		workspace.read("day_1/src/main.rs"),
+		"fn main() {\n\tprintln!(\"2022 1 day_1\");\n}\n"
+	);
+	assert_eq!(workspace.read("day_1/src/parse.rs"), "// day_1/input.txt\n");
+}
+
+#[test]
+fn by_year_layout() {
+	let server = StandIn::start();
+	server.route_calendar(2021, Some("tester"));
+	server.route("GET", "/2021/day/1/input", 200, INPUT);
+
+	let workspace = TestWorkspace::new(&["aqa_aoc", "year_2022/day_1"]);
+	workspace.write_cfg(&format!(
+		"base_url = \"{}\"\nlayout = \"by_year\"\n",
+		server.addr
+	));
+	workspace.write(
+		"aoc_common/Cargo.toml",
+		"[package]\nname = \"aoc_common\"\n",
+	);
+
+	assert_success(&workspace.run(&["--year", "2021", "--day", "1"]));
+
+	assert_eq!(
+		workspace.read("Cargo.toml"),
+		"[workspace]\nmembers = [\n    'aqa_aoc',\n    'year_2021/day_1',\n    'year_2022/day_1',\n]\n"
+	);
+	let day_cargo_toml: toml::Value =
+		toml::from_str(&workspace.read("year_2021/day_1/Cargo.toml")).unwrap();
+	assert_eq!(
+		day_cargo_toml["package"]["name"].as_str(),
+		Some("year_2021_day_1")
+	);
+	assert!(workspace
+		.read("year_2021/day_1/src/main.rs")
+		.contains("aoc_common::main::<year_2021_day_1::Day>"));
+	assert_eq!(
+		day_cargo_toml["dependencies"]["aoc_common"]["path"].as_str(),
+		Some("../../aoc_common")
+	);
+	assert_eq!(workspace.read("year_2021/day_1/input.txt"), INPUT);
+
+	let output = workspace.run(&["status", "--year", "2021", "--offline"]);
+	assert_success(&output);
+	assert!(String::from_utf8_lossy(&output.stdout)
+		.contains("year_2021/day_1  yes    yes     yes    0               - -      ?\n"));
+}
+
+#[test]
+fn wait_retries_until_the_input_is_out() {
+	let server = StandIn::start();
+	server.route_calendar(2022, Some("tester"));
+
+	let workspace = TestWorkspace::new(&[]);
+	workspace.write_cfg(&format!("base_url = \"{}\"\n", server.addr));
+
+	let output = std::thread::scope(|scope| {
+		scope.spawn(|| {
+			std::thread::sleep(std::time::Duration::from_millis(300));
+			server.route("GET", "/2022/day/1/input", 200, INPUT);
+		});
+		workspace.run(&["--day=1", "--wait"])
+	});
+	assert_success(&output);
+	assert!(String::from_utf8_lossy(&output.stderr).contains("retrying in 1s"));
+
+	assert_eq!(workspace.read("day_1/input.txt"), INPUT);
+	let input_requests = server
+		.requests()
+		.iter()
+		.filter(|request| request.path == "/2022/day/1/input")
+		.count();
+	assert_eq!(input_requests, 2);
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use common::{assert_success, TestWorkspace};
+use std::process::Command;
+
+fn git(workspace: &TestWorkspace, args: &[&str]) -> String {
+	let output = Command::new("git")
+		.args(args)
+		.current_dir(workspace.path())
+		.output()
+		.unwrap();
+	assert_success(&output);
+	String::from_utf8(output.stdout).unwrap()
+}
+
+#[cfg(unix)]
+#[test]
+fn migrate_inputs_out_of_git() {
+	let workspace = TestWorkspace::new(&["day_1", "day_2", "tools"]);
+	workspace.write_cfg("");
+	workspace.add_day_crate(1);
+	workspace.add_day_crate(2);
+	workspace.write("day_1/input.txt", "1\n");
+	workspace.write("day_1/input.meta.toml", "year = 2022\n");
+	workspace.write("tools/input.txt", "not an input\n");
+	git(&workspace, &["init", "--quiet"]);
+	git(&workspace, &["add", "."]);
+
+	let output = workspace.run(&["migrate-inputs"]);
+	assert_success(&output);
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	assert!(stdout.contains("Moved day_1/input.txt to ./.aqa_aoc/inputs/day_1/input.txt\n"));
+
+	assert_eq!(workspace.read("day_1/input.txt"), "1\n");
+	assert_eq!(workspace.read(".aqa_aoc/inputs/day_1/input.txt"), "1\n");
+	assert_eq!(workspace.read("tools/input.txt"), "not an input\n");
+	assert!(workspace
+		.read("aoc_cfg.toml")
+		.ends_with("inputs = \"cache\"\n"));
+
+	let tracked = git(&workspace, &["ls-files"]);
+	assert!(!tracked.contains("day_1/input.txt"));
+	assert!(!tracked.contains("day_1/input.meta.toml"));
+	assert!(tracked.contains("tools/input.txt"));
+	assert!(
+		git(&workspace, &["status", "--porcelain", "--ignored", "day_1"])
+			.contains("!! day_1/input.txt\n")
+	);
+	assert!(
+		git(&workspace, &["status", "--porcelain", "--ignored", "day_1"])
+			.contains("!! day_1/input.meta.toml\n")
+	);
+
+	// Nothing left to move
+	let output = workspace.run(&["migrate-inputs"]);
+	assert_success(&output);
+	assert!(String::from_utf8_lossy(&output.stdout).contains("No inputs to move\n"));
+}
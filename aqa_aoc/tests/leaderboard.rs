@@ -0,0 +1,63 @@
+mod common;
+
+use common::{assert_success, StandIn, TestWorkspace};
+
+const LEADERBOARD: &str = r#"{"owner_id": 7, "event": "2022", "members": {
+	"7": {"id": 7, "name": "tester", "stars": 2, "local_score": 4, "global_score": 0, "last_star_ts": 1669871400,
+		"completion_day_level": {"1": {"1": {"get_star_ts": 1669870860, "star_index": 0}, "2": {"get_star_ts": 1669871400, "star_index": 1}}}},
+	"8": {"id": 8, "name": null, "stars": 1, "local_score": 1, "global_score": 0, "last_star_ts": 1669874400,
+		"completion_day_level": {"1": {"1": {"get_star_ts": 1669874400, "star_index": 2}}}}
+}}"#;
+
+#[test]
+fn leaderboard_is_cached() {
+	let server = StandIn::start();
+	server.route(
+		"GET",
+		"/2022/leaderboard/private/view/7.json",
+		200,
+		LEADERBOARD,
+	);
+
+	let workspace = TestWorkspace::new(&[]);
+	workspace.write_cfg(&format!("base_url = \"{}\"\n", server.addr));
+
+	let output = workspace.run(&["leaderboard", "7"]);
+	assert_success(&output);
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	assert!(stdout.contains("  1      4      2  tester\n"));
+	assert!(stdout.contains("  tester                 00:01:00    00:10:00    00:09:00\n"));
+	assert!(stdout.contains("  (anonymous user #8)    01:00:00\n"));
+
+	let output = workspace.run(&["leaderboard", "--format", "csv", "7"]);
+	assert_success(&output);
+	assert_eq!(
+		String::from_utf8_lossy(&output.stdout),
+		"rank,id,name,local_score,stars,day,part_1,part_2,delta
+1,7,tester,4,2,1,1669870860,1669871400,540
+2,8,(anonymous user #8),1,1,1,1669874400,,
+"
+	);
+
+	let requests = server.requests();
+	assert_eq!(requests.len(), 1);
+	assert_eq!(requests[0].path, "/2022/leaderboard/private/view/7.json");
+}
+
+#[test]
+fn login_page_is_not_cached() {
+	let server = StandIn::start();
+	server.route(
+		"GET",
+		"/2022/leaderboard/private/view/7.json",
+		200,
+		"<html><body>[Log In]</body></html>",
+	);
+
+	let workspace = TestWorkspace::new(&[]);
+	workspace.write_cfg(&format!("base_url = \"{}\"\n", server.addr));
+
+	let output = workspace.run(&["leaderboard", "7"]);
+	assert_eq!(output.status.code(), Some(7));
+	assert!(!workspace.file(".aqa_aoc/leaderboard/2022_7.json").exists());
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use common::{assert_success, TestWorkspace};
+
+fn workspace_with_solution(main: &str) -> TestWorkspace {
+	let workspace = TestWorkspace::new(&["day_1"]);
+	workspace.write_cfg("");
+	workspace.add_day_crate(1);
+	workspace.write("day_1/src/main.rs", main);
+	workspace
+}
+
+#[test]
+fn run_checks_answers() {
+	// Reports the time of part 1 only, like aoc_common::main does for both
+	let workspace = workspace_with_solution(
+		r#"fn main() {
+	let part = std::env::var("AOC_PART").ok();
+	println!("parsing");
+	if part.as_deref() != Some("2") {
+		if std::env::var_os("AOC_TIMINGS").is_some() {
+			println!("Time 1: 1500000ns");
+		}
+		println!("Part 1: 24000");
+	}
+	if part.as_deref() != Some("1") {
+		println!("Part 2: 45000");
+	} else {
+		println!("skipped part 2");
+	}
+}
+"#,
+	);
+	workspace.write("day_1/input.txt", "1\n");
+	workspace.write(
+		"day_1/answers.toml",
+		"[part_1]\nanswer = \"24000\"\n\n[part_2]\nanswer = \"45001\"\n",
+	);
+
+	let output = workspace.run(&["run", "--day", "1"]);
+	assert!(!output.status.success());
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	assert!(stdout.contains("parsing\n"));
+	assert!(stdout.contains("Part 1: 24000  (1.50ms)  correct\n"));
+	assert!(stdout.contains("Part 2: 45000  wrong, the answer is 45001\n"));
+	assert!(String::from_utf8_lossy(&output.stderr).contains("part 2 doesn't match answers.toml"));
+
+	let output = workspace.run(&["run", "--day", "1", "--part", "1"]);
+	assert_success(&output);
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	assert!(stdout.contains("Part 1: 24000"));
+	assert!(stdout.contains("skipped part 2\n"));
+	assert!(!stdout.contains("Part 2"));
+}
+
+#[test]
+fn run_without_input() {
+	let workspace = workspace_with_solution("fn main() {}\n");
+
+	let output = workspace.run(&["run", "--day", "1"]);
+	assert!(!output.status.success());
+	assert!(String::from_utf8_lossy(&output.stderr).contains(
+		"day_1/input.txt is missing, download it with `aqa_aoc fetch --day 1 --year 2022`"
+	));
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use common::{assert_success, StandIn, TestWorkspace, SESSION_COOKIE};
+
+#[test]
+fn whoami_reports_user_and_source() {
+	let server = StandIn::start();
+	server.route_calendar(2022, Some("tester"));
+
+	let workspace = TestWorkspace::new(&[]);
+	workspace.write(
+		"aoc_cfg.toml",
+		&format!(
+			"year = 2022\nrequest_delay_ms = 0\nbase_url = \"{}\"\nsession_command = \"echo {}\"\n",
+			server.addr, SESSION_COOKIE
+		),
+	);
+
+	let output = workspace.run(&["whoami"]);
+	assert_success(&output);
+	assert!(String::from_utf8_lossy(&output.stdout).contains(&format!(
+		"Logged in as tester, session cookie from the output of `echo {}`",
+		SESSION_COOKIE
+	)));
+	assert_eq!(
+		server.requests()[0].header("Cookie"),
+		Some(format!("session={}", SESSION_COOKIE).as_str())
+	);
+
+	let output = workspace
+		.command()
+		.arg("whoami")
+		.env("AQA_AOC_SESSION", "from_env")
+		.output()
+		.unwrap();
+	assert_success(&output);
+	assert!(String::from_utf8_lossy(&output.stdout).contains("the AQA_AOC_SESSION variable"));
+	assert_eq!(
+		server.requests()[1].header("Cookie"),
+		Some("session=from_env")
+	);
+}
+
+#[test]
+fn reads_session_from_config_dir() {
+	let server = StandIn::start();
+	server.route_calendar(2022, Some("tester"));
+
+	let workspace = TestWorkspace::new(&[]);
+	workspace.write(
+		"aoc_cfg.toml",
+		&format!(
+			"year = 2022\nrequest_delay_ms = 0\nbase_url = \"{}\"\n",
+			server.addr
+		),
+	);
+
+	let output = workspace.run(&["whoami"]);
+	assert!(!output.status.success());
+	assert!(String::from_utf8_lossy(&output.stderr).contains("no session cookie found"));
+	assert!(server.requests().is_empty());
+
+	workspace.write(".config/aqa_aoc/session", &format!("{}\n", SESSION_COOKIE));
+	assert_success(&workspace.run(&["whoami"]));
+	assert_eq!(
+		server.requests()[0].header("Cookie"),
+		Some(format!("session={}", SESSION_COOKIE).as_str())
+	);
+}
+
+#[test]
+fn expired_session_stops_input_download() {
+	let server = StandIn::start();
+	server.route_calendar(2022, None);
+	server.route(
+		"GET",
+		"/2022/day/1/input",
+		400,
+		"Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+	);
+
+	let workspace = TestWorkspace::new(&["day_1"]);
+	workspace.write_cfg(&format!("base_url = \"{}\"\n", server.addr));
+	workspace.add_day_crate(1);
+
+	let output = workspace.run(&["fetch", "--day=1"]);
+	assert_eq!(output.status.code(), Some(4));
+	assert!(String::from_utf8_lossy(&output.stderr).contains(
+		"the session cookie from `session_cookie` in aoc_cfg.toml is expired or invalid"
+	));
+	// Only the input was requested, not the calendar as well
+	let requests = server.requests();
+	assert_eq!(requests.len(), 1);
+	assert_eq!(requests[0].path, "/2022/day/1/input");
+	assert!(!workspace.file("day_1/input.txt").exists());
+
+	let output = workspace.run(&["whoami"]);
+	assert_eq!(output.status.code(), Some(4));
+}
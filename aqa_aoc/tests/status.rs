@@ -0,0 +1,32 @@
+mod common;
+
+use common::{assert_success, StandIn, TestWorkspace};
+
+#[test]
+fn status_shows_every_day() {
+	let server = StandIn::start();
+	server.route(
+		"GET",
+		"/2022",
+		200,
+		r#"<html><body><header><div><div class="user">tester</div></div></header><main><pre class="calendar">
+<a aria-label="Day 2, one star" href="/2022/day/2" class="calendar-day2 calendar-complete"></a>
+<a aria-label="Day 1, two stars" href="/2022/day/1" class="calendar-day1 calendar-verycomplete"></a>
+</pre></main></body></html>"#,
+	);
+
+	let workspace = TestWorkspace::new(&["aqa_aoc", "day_1", "day_2"]);
+	workspace.write_cfg(&format!("base_url = \"{}\"\n", server.addr));
+	workspace.add_day_crate(1);
+	workspace.write("day_1/input.txt", "1\n");
+	workspace.write(
+		"day_1/answers.toml",
+		"[part_1]\nanswer = \"1\"\n\n[part_2]\nanswer = \"2\"\n",
+	);
+
+	let output = workspace.run(&["status"]);
+	assert_success(&output);
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	assert!(stdout.contains("day_1  yes    yes     yes    0               1 2      **\n"));
+	assert!(stdout.contains("day_2  no     yes     no     0               - -      *\n"));
+}
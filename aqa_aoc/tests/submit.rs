@@ -0,0 +1,59 @@
+mod common;
+
+use common::{assert_success, StandIn, TestWorkspace};
+
+fn answer_page(article: &str) -> String {
+	format!(
+		"<html><body><main><article><p>{}</p></article></main></body></html>",
+		article
+	)
+}
+
+#[test]
+fn ledger_refuses_known_wrong_answers() {
+	let server = StandIn::start();
+	server.route(
+		"POST",
+		"/2022/day/1/answer",
+		200,
+		answer_page("That's not the right answer; your answer is too high."),
+	);
+
+	let workspace = TestWorkspace::new(&["day_1"]);
+	workspace.write_cfg(&format!("base_url = \"{}\"\n", server.addr));
+	workspace.add_day_crate(1);
+
+	let output = workspace.run(&["submit", "--day=1", "--part=1", "100"]);
+	assert_success(&output);
+	let ledger: toml::Value = toml::from_str(&workspace.read("day_1/answers.toml")).unwrap();
+	assert_eq!(ledger["part_1"]["too_high"].as_integer(), Some(100));
+	assert_eq!(
+		ledger["part_1"]["attempts"][0]["outcome"].as_str(),
+		Some("too_high")
+	);
+
+	for answer in ["100", "150"] {
+		let output = workspace.run(&["submit", "--day=1", "--part=1", answer]);
+		assert_eq!(output.status.code(), Some(1));
+		assert!(String::from_utf8_lossy(&output.stderr).contains("Use --force to submit it anyway"));
+	}
+	assert_eq!(server.requests().len(), 1);
+
+	server.route(
+		"POST",
+		"/2022/day/1/answer",
+		200,
+		answer_page("That's the right answer!"),
+	);
+	let output = workspace.run(&["submit", "--day=1", "--part=1", "--force", " 150\n"]);
+	assert_success(&output);
+	let posts = server
+		.requests()
+		.into_iter()
+		.filter(|request| request.method == "POST")
+		.collect::<Vec<_>>();
+	assert_eq!(posts.len(), 2);
+	assert_eq!(posts[1].body, "level=1&answer=150");
+	let ledger: toml::Value = toml::from_str(&workspace.read("day_1/answers.toml")).unwrap();
+	assert_eq!(ledger["part_1"]["answer"].as_str(), Some("150"));
+}
@@ -0,0 +1,52 @@
+use anyhow::{anyhow, Context};
+use aoc_common::Solution;
+use itertools::Itertools;
+use std::fmt::Display;
+
+pub struct Day;
+
+impl Solution for Day {
+	const DAY: u32 = 1;
+
+	type Input = Vec<u64>;
+
+	fn parse(input: &str) -> anyhow::Result<Vec<u64>> {
+		parse_input(input)
+	}
+
+	fn part_1(elves: &Vec<u64>) -> anyhow::Result<impl Display> {
+		part_1(elves)
+	}
+
+	fn part_2(elves: &Vec<u64>) -> anyhow::Result<impl Display> {
+		Ok(part_2(elves))
+	}
+}
+
+/// The calories carried by each elf.
+fn parse_input(input: &str) -> anyhow::Result<Vec<u64>> {
+	input
+		.trim_end()
+		.split("\n\n")
+		.map(|elf| {
+			elf.lines()
+				.map(|num| {
+					num.parse::<u64>()
+						.with_context(|| format!("expected calories, got `{}`", num))
+				})
+				.sum()
+		})
+		.collect()
+}
+
+fn part_1(elves: &[u64]) -> anyhow::Result<u64> {
+	elves
+		.iter()
+		.copied()
+		.max()
+		.ok_or_else(|| anyhow!("malformed input"))
+}
+
+fn part_2(elves: &[u64]) -> u64 {
+	elves.iter().sorted().rev().take(3).sum()
+}
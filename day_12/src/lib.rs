@@ -0,0 +1,120 @@
+use anyhow::{anyhow, bail};
+use aoc_common::grid::{Grid, Pos};
+use aoc_common::Solution;
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Display;
+
+pub struct Day;
+
+impl Solution for Day {
+	const DAY: u32 = 12;
+
+	type Input = Heightmap;
+
+	fn parse(input: &str) -> anyhow::Result<Heightmap> {
+		parse_input(input)
+	}
+
+	fn part_1(heightmap: &Heightmap) -> anyhow::Result<impl Display> {
+		find_shortest_path_from_to(&heightmap.grid, heightmap.start, heightmap.end)
+			.ok_or_else(|| anyhow!("there is no path to the best signal"))
+	}
+
+	fn part_2(heightmap: &Heightmap) -> anyhow::Result<impl Display> {
+		Ok(part_2(heightmap))
+	}
+}
+
+pub struct Heightmap {
+	grid: Grid<u8>,
+	start: Pos,
+	end: Pos,
+}
+
+fn parse_input(input: &str) -> anyhow::Result<Heightmap> {
+	let mut grid = Grid::parse(input, |c| match c {
+		'a'..='z' | 'S' | 'E' => Ok(c as u8),
+		_ => bail!("expected a height, got `{}`", c),
+	})?;
+
+	let start = grid
+		.find(&b'S')
+		.ok_or_else(|| anyhow!("missing the start"))?;
+	let end = grid.find(&b'E').ok_or_else(|| anyhow!("missing the end"))?;
+	grid[start] = b'a';
+	grid[end] = b'z';
+
+	Ok(Heightmap { grid, start, end })
+}
+
+fn part_2(heightmap: &Heightmap) -> u64 {
+	let mut min = u64::MAX;
+	for (position, height) in heightmap.grid.iter() {
+		if *height == b'a' {
+			if let Some(v) = find_shortest_path_from_to(&heightmap.grid, position, heightmap.end) {
+				if v < min {
+					min = v;
+				}
+			}
+		}
+	}
+	min
+}
+
+fn find_shortest_path_from_to(grid: &Grid<u8>, start: Pos, end: Pos) -> Option<u64> {
+	let mut queue = VecDeque::new();
+
+	let mut visited = HashSet::new();
+
+	queue.extend(get_adjacent(grid, start).into_iter().map(|x| (x, 1)));
+
+	while let Some((position, level)) = queue.pop_front() {
+		if visited.contains(&position) {
+			continue;
+		}
+
+		if position == end {
+			return Some(level);
+		} else {
+			get_adjacent(grid, position)
+				.into_iter()
+				.for_each(|pos| queue.push_back((pos, level + 1)));
+			visited.insert(position);
+		}
+	}
+
+	None
+}
+
+fn get_adjacent(grid: &Grid<u8>, position: Pos) -> Vec<Pos> {
+	let current = grid[position];
+	grid.neighbours_4(position)
+		.filter(|(_, height)| height.saturating_sub(current) <= 1)
+		.map(|(pos, _)| pos)
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	const EXAMPLE: &str = "Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi
+";
+
+	fn find_shortest_path(input: &str) -> u64 {
+		let heightmap = super::parse_input(input).unwrap();
+		super::find_shortest_path_from_to(&heightmap.grid, heightmap.start, heightmap.end).unwrap()
+	}
+
+	#[test]
+	fn part_1() {
+		assert_eq!(find_shortest_path(EXAMPLE), 31);
+	}
+
+	#[test]
+	fn part_2() {
+		assert_eq!(super::part_2(&super::parse_input(EXAMPLE).unwrap()), 29);
+	}
+}
@@ -0,0 +1,173 @@
+use anyhow::{anyhow, bail};
+use aoc_common::Solution;
+use itertools::Itertools;
+use nom::sequence::delimited;
+use nom::IResult;
+use std::cmp::Ordering;
+use std::fmt::Display;
+
+pub struct Day;
+
+impl Solution for Day {
+	const DAY: u32 = 13;
+
+	type Input = Vec<(List, List)>;
+
+	fn parse(input: &str) -> anyhow::Result<Vec<(List, List)>> {
+		parse_input(input)
+	}
+
+	fn part_1(pairs: &Vec<(List, List)>) -> anyhow::Result<impl Display> {
+		Ok(part_1(pairs))
+	}
+
+	fn part_2(pairs: &Vec<(List, List)>) -> anyhow::Result<impl Display> {
+		Ok(part_2(pairs))
+	}
+}
+
+/// The pairs of packets, separated by blank lines.
+fn parse_input(input: &str) -> anyhow::Result<Vec<(List, List)>> {
+	let parse_packet = |line| {
+		parse_list(line)
+			.map_err(|err| anyhow!("{err:?}"))
+			.map(|(_, packet)| packet)
+	};
+	input
+		.trim()
+		.split("\n\n")
+		.map(|lines| {
+			let Some((left, right)) = lines.lines().collect_tuple() else {
+				bail!("expected a pair of packets, got `{}`", lines);
+			};
+			Ok((parse_packet(left)?, parse_packet(right)?))
+		})
+		.collect()
+}
+
+fn part_1(pairs: &[(List, List)]) -> usize {
+	let mut sum = 0;
+	for (idx, (left, right)) in pairs.iter().enumerate() {
+		if left <= right {
+			sum += idx + 1;
+		}
+	}
+	sum
+}
+
+fn part_2(pairs: &[(List, List)]) -> usize {
+	let additional_packet_1 = List::List(vec![List::List(vec![List::Integer(2)])]);
+	let additional_packet_2 = List::List(vec![List::List(vec![List::Integer(6)])]);
+
+	let mut packets = pairs
+		.iter()
+		.flat_map(|(left, right)| [left.clone(), right.clone()])
+		.collect::<Vec<_>>();
+
+	packets.push(additional_packet_1.clone());
+	packets.push(additional_packet_2.clone());
+
+	packets.sort();
+
+	(packets
+		.iter()
+		.position(|packet| packet == &additional_packet_1)
+		.unwrap()
+		+ 1) * (packets
+		.iter()
+		.position(|packet| packet == &additional_packet_2)
+		.unwrap()
+		+ 1)
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum List {
+	List(Vec<List>),
+	Integer(u64),
+}
+
+impl Ord for List {
+	fn cmp(&self, right: &Self) -> Ordering {
+		match (self, right) {
+			(List::Integer(n), List::Integer(m)) => n.cmp(m),
+			(List::List(l), List::List(l2)) => {
+				let mut l_iter = l.iter();
+				let mut l2_iter = l2.iter();
+				loop {
+					match (l_iter.next(), l2_iter.next()) {
+						(Some(l_item), Some(l2_item)) => match l_item.cmp(l2_item) {
+							Ordering::Equal => (),
+							ordering => return ordering,
+						},
+						(Some(_), None) => return Ordering::Greater,
+						(None, Some(_)) => return Ordering::Less,
+						(None, None) => return Ordering::Equal,
+					}
+				}
+			}
+			(l @ List::List(_), List::Integer(n)) => l.cmp(&List::List(vec![List::Integer(*n)])),
+			(List::Integer(n), l @ List::List(_)) => List::List(vec![List::Integer(*n)]).cmp(l),
+		}
+	}
+}
+
+impl PartialOrd for List {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+fn parse_list(input: &str) -> IResult<&str, List> {
+	use nom::branch::alt;
+	use nom::bytes::complete::tag;
+	use nom::character::complete::{char, u64};
+	use nom::combinator::map;
+	use nom::multi::separated_list0;
+
+	delimited(
+		char('['),
+		map(
+			separated_list0(tag(","), alt((map(u64, List::Integer), parse_list))),
+			List::List,
+		),
+		char(']'),
+	)(input)
+}
+
+#[cfg(test)]
+mod tests {
+	const EXAMPLE: &str = "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]
+";
+
+	#[test]
+	fn part_1_example_1() {
+		assert_eq!(super::part_1(&super::parse_input(EXAMPLE).unwrap()), 13);
+	}
+
+	#[test]
+	fn part_2_example_1() {
+		assert_eq!(super::part_2(&super::parse_input(EXAMPLE).unwrap()), 140);
+	}
+}
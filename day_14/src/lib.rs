@@ -0,0 +1,151 @@
+use anyhow::{anyhow, bail};
+use aoc_common::grid::SparseGrid;
+use aoc_common::Solution;
+use itertools::Itertools;
+use std::fmt::Display;
+
+pub struct Day;
+
+impl Solution for Day {
+	const DAY: u32 = 14;
+
+	type Input = SparseGrid<Point>;
+
+	fn parse(input: &str) -> anyhow::Result<SparseGrid<Point>> {
+		parse_input(input)
+	}
+
+	fn part_1(rocks: &SparseGrid<Point>) -> anyhow::Result<impl Display> {
+		Ok(part_1(rocks.clone()))
+	}
+
+	fn part_2(rocks: &SparseGrid<Point>) -> anyhow::Result<impl Display> {
+		Ok(part_2(rocks.clone()))
+	}
+}
+
+fn part_1(mut grid: SparseGrid<Point>) -> usize {
+	let sand_producer = (500, 0);
+	let (_, (_, lowest_point)) = grid.bounds().unwrap();
+
+	'l: loop {
+		let mut sand = sand_producer;
+
+		'falling_loop: loop {
+			for (dx, dy) in [(0, 1), (-1, 1), (1, 1)] {
+				let (nx, ny) = (sand.0 + dx, sand.1 + dy);
+				match grid.get((nx, ny)) {
+					Some(Point::Rock | Point::RestingSand) => {
+						continue;
+					}
+					None => {
+						if ny > lowest_point {
+							break 'l;
+						}
+						sand = (nx, ny);
+						continue 'falling_loop;
+					}
+				}
+			}
+			grid.insert(sand, Point::RestingSand);
+			break 'falling_loop;
+		}
+	}
+
+	grid.iter()
+		.filter(|(_, p)| matches!(p, Point::RestingSand))
+		.count()
+}
+
+fn part_2(mut grid: SparseGrid<Point>) -> usize {
+	let sand_producer = (500, 0);
+	let (_, (_, lowest_rock)) = grid.bounds().unwrap();
+	let lowest_point = 2 + lowest_rock;
+
+	'l: loop {
+		let mut sand = sand_producer;
+
+		'falling_loop: loop {
+			for (dx, dy) in [(0, 1), (-1, 1), (1, 1)] {
+				let (nx, ny) = (sand.0 + dx, sand.1 + dy);
+				match grid.get((nx, ny)) {
+					Some(Point::Rock | Point::RestingSand) => {
+						continue;
+					}
+					None => {
+						if ny == lowest_point {
+							continue;
+						}
+						sand = (nx, ny);
+						continue 'falling_loop;
+					}
+				}
+			}
+			grid.insert(sand, Point::RestingSand);
+			if sand == sand_producer {
+				break 'l;
+			}
+			break 'falling_loop;
+		}
+	}
+
+	grid.iter()
+		.filter(|(_, p)| matches!(p, Point::RestingSand))
+		.count()
+}
+
+/// The rocks along the paths in the scan.
+fn parse_input(input: &str) -> anyhow::Result<SparseGrid<Point>> {
+	let mut grid = SparseGrid::new();
+	for line in parse_paths(input)? {
+		for ((a_x, a_y), (b_x, b_y)) in line.into_iter().tuple_windows() {
+			for y in (a_y.min(b_y))..=(a_y.max(b_y)) {
+				for x in (a_x.min(b_x))..=(a_x.max(b_x)) {
+					grid.insert((x, y), Point::Rock);
+				}
+			}
+		}
+	}
+	if grid.is_empty() {
+		bail!("no rocks in the scan");
+	}
+	Ok(grid)
+}
+
+fn parse_paths(input: &str) -> anyhow::Result<Vec<Vec<(i64, i64)>>> {
+	use nom::bytes::complete::tag;
+	use nom::character::complete::{char, i64, multispace0};
+	use nom::combinator::all_consuming;
+	use nom::multi::{many1, separated_list1};
+	use nom::sequence::{separated_pair, terminated};
+
+	all_consuming(many1(terminated(
+		separated_list1(tag(" -> "), separated_pair(i64, char(','), i64)),
+		multispace0,
+	)))(input)
+	.map(|(_input, res)| res)
+	.map_err(|err: nom::Err<nom::error::Error<&str>>| anyhow!("{err:?}"))
+}
+
+#[derive(Hash, Copy, Clone)]
+pub enum Point {
+	Rock,
+	RestingSand,
+}
+
+#[cfg(test)]
+mod tests {
+	const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9
+";
+
+	#[test]
+	fn part_1_example_1() {
+		assert_eq!(super::part_1(super::parse_input(EXAMPLE).unwrap()), 24);
+	}
+
+	#[test]
+	fn part_2_example_1() {
+		assert_eq!(super::part_2(super::parse_input(EXAMPLE).unwrap()), 93);
+	}
+}
@@ -0,0 +1,143 @@
+use anyhow::Context;
+use aoc_common::Solution;
+use itertools::Itertools;
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Display;
+
+pub struct Day;
+
+impl Solution for Day {
+	const DAY: u32 = 18;
+
+	type Input = HashSet<(i64, i64, i64)>;
+
+	fn parse(input: &str) -> anyhow::Result<HashSet<(i64, i64, i64)>> {
+		parse_input(input)
+	}
+
+	fn part_1(cubes: &HashSet<(i64, i64, i64)>) -> anyhow::Result<impl Display> {
+		Ok(part_1(cubes))
+	}
+
+	fn part_2(cubes: &HashSet<(i64, i64, i64)>) -> anyhow::Result<impl Display> {
+		Ok(part_2(cubes))
+	}
+}
+
+static SIDES: [(i64, i64, i64); 6] = [
+	(1, 0, 0),
+	(-1, 0, 0),
+	(0, 1, 0),
+	(0, -1, 0),
+	(0, 0, 1),
+	(0, 0, -1),
+];
+
+fn parse_input(input: &str) -> anyhow::Result<HashSet<(i64, i64, i64)>> {
+	input
+		.trim()
+		.lines()
+		.map(|line| {
+			line.split(',')
+				.map(|n| n.parse::<i64>())
+				.collect_tuple()
+				.and_then(|(x, y, z)| Some((x.ok()?, y.ok()?, z.ok()?)))
+				.with_context(|| format!("expected a cube like `1,2,3`, got `{}`", line))
+		})
+		.collect()
+}
+
+fn part_1(cubes: &HashSet<(i64, i64, i64)>) -> usize {
+	cubes
+		.iter()
+		.flat_map(|(x, y, z)| {
+			SIDES
+				.iter()
+				.map(|(dx, dy, dz)| (*x + *dx, *y + *dy, *z + *dz))
+		})
+		.filter(|cube| !cubes.contains(cube))
+		.count()
+}
+
+fn part_2(cubes: &HashSet<(i64, i64, i64)>) -> u64 {
+	let [mut min_x, mut min_y, mut min_z] = [i64::MAX; 3];
+	let [mut max_x, mut max_y, mut max_z] = [0; 3];
+	cubes.iter().for_each(|(x, y, z)| {
+		min_x = min_x.min(*x);
+		min_y = min_y.min(*y);
+		min_z = min_z.min(*z);
+		max_x = max_x.max(*x);
+		max_y = max_y.max(*y);
+		max_z = max_z.max(*z);
+	});
+
+	min_x -= 1;
+	min_y -= 1;
+	min_z -= 1;
+	max_x += 1;
+	max_y += 1;
+	max_z += 1;
+
+	let mut q = VecDeque::new();
+	q.push_back((min_x, min_y, min_z));
+
+	let mut visited = HashSet::new();
+	let mut surface_area = 0;
+
+	while let Some(cube @ (x, y, z)) = q.pop_front() {
+		if visited.contains(&cube) {
+			continue;
+		}
+		visited.insert(cube);
+
+		for (dx, dy, dz) in &SIDES {
+			let cube2 = (x + dx, y + dy, z + dz);
+
+			if cube2.0 < min_x
+				|| cube2.0 > max_x
+				|| cube2.1 < min_y
+				|| cube2.1 > max_y
+				|| cube2.2 < min_z
+				|| cube2.2 > max_z
+			{
+				continue;
+			}
+
+			if cubes.contains(&cube2) {
+				surface_area += 1;
+			} else {
+				q.push_back(cube2);
+			}
+		}
+	}
+
+	surface_area
+}
+
+#[cfg(test)]
+mod tests {
+	static EXAMPLE: &str = "2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5
+";
+
+	#[test]
+	fn part_1_example_1() {
+		assert_eq!(super::part_1(&super::parse_input(EXAMPLE).unwrap()), 64);
+	}
+
+	#[test]
+	fn part_2_example_1() {
+		assert_eq!(super::part_2(&super::parse_input(EXAMPLE).unwrap()), 58);
+	}
+}
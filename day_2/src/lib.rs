@@ -0,0 +1,84 @@
+#![allow(clippy::identity_op)]
+
+use anyhow::bail;
+use aoc_common::Solution;
+use std::fmt::Display;
+
+pub struct Day;
+
+impl Solution for Day {
+	const DAY: u32 = 2;
+
+	type Input = Vec<(u8, u8)>;
+
+	fn parse(input: &str) -> anyhow::Result<Vec<(u8, u8)>> {
+		parse_input(input)
+	}
+
+	fn part_1(rounds: &Vec<(u8, u8)>) -> anyhow::Result<impl Display> {
+		Ok(part_1(rounds))
+	}
+
+	fn part_2(rounds: &Vec<(u8, u8)>) -> anyhow::Result<impl Display> {
+		Ok(part_2(rounds))
+	}
+}
+
+/// The rounds, the move of the opponent (`A` to `C`) with the second column (`X` to `Z`).
+fn parse_input(input: &str) -> anyhow::Result<Vec<(u8, u8)>> {
+	input
+		.lines()
+		.map(|line| match line.as_bytes() {
+			[opponent @ b'A'..=b'C', b' ', second @ b'X'..=b'Z'] => Ok((*opponent, *second)),
+			_ => bail!("expected a round like `A Y`, got `{}`", line),
+		})
+		.collect()
+}
+
+fn part_1(rounds: &[(u8, u8)]) -> u64 {
+	let mut total = 0;
+
+	for &(opponent_move, my_move) in rounds {
+		total += match (opponent_move, my_move) {
+			(b'A', b'X') => 1 + 3,
+			(b'A', b'Y') => 2 + 6,
+			(b'A', b'Z') => 3 + 0,
+
+			(b'B', b'X') => 1 + 0,
+			(b'B', b'Y') => 2 + 3,
+			(b'B', b'Z') => 3 + 6,
+
+			(b'C', b'X') => 1 + 6,
+			(b'C', b'Y') => 2 + 0,
+			(b'C', b'Z') => 3 + 3,
+
+			_ => unreachable!("{} {}", opponent_move as char, my_move as char),
+		};
+	}
+
+	total
+}
+
+fn part_2(rounds: &[(u8, u8)]) -> u64 {
+	let mut total = 0;
+
+	for &(opponent_move, result) in rounds {
+		total += match (opponent_move, result) {
+			(b'A', b'X') => 3 + 0,
+			(b'A', b'Y') => 1 + 3,
+			(b'A', b'Z') => 2 + 6,
+
+			(b'B', b'X') => 1 + 0,
+			(b'B', b'Y') => 2 + 3,
+			(b'B', b'Z') => 3 + 6,
+
+			(b'C', b'X') => 2 + 0,
+			(b'C', b'Y') => 3 + 3,
+			(b'C', b'Z') => 1 + 6,
+
+			_ => unreachable!("{} {}", opponent_move as char, result as char),
+		};
+	}
+
+	total
+}
@@ -0,0 +1,113 @@
+use anyhow::Context;
+use aoc_common::Solution;
+use itertools::Itertools;
+use std::fmt::Display;
+
+pub struct Day;
+
+impl Solution for Day {
+	const DAY: u32 = 20;
+
+	type Input = Vec<i64>;
+
+	fn parse(input: &str) -> anyhow::Result<Vec<i64>> {
+		parse_input(input)
+	}
+
+	fn part_1(file: &Vec<i64>) -> anyhow::Result<impl Display> {
+		Ok(part_1(file))
+	}
+
+	fn part_2(file: &Vec<i64>) -> anyhow::Result<impl Display> {
+		Ok(part_2(file))
+	}
+}
+
+/// The numbers of the encrypted file.
+fn parse_input(input: &str) -> anyhow::Result<Vec<i64>> {
+	input
+		.trim()
+		.lines()
+		.map(|n| {
+			n.parse()
+				.with_context(|| format!("expected a number, got `{}`", n))
+		})
+		.try_collect()
+}
+
+fn part_1(original_list: &[i64]) -> i64 {
+	let len = original_list.len() as i64;
+	let mut list: Vec<_> = original_list.iter().copied().enumerate().collect();
+
+	for (idx, _) in original_list.iter().enumerate() {
+		let item_idx = list
+			.iter()
+			.position(|(original_idx, _)| *original_idx == idx)
+			.unwrap();
+		let item = list.remove(item_idx);
+		let new_idx = (item_idx as i64 + item.1).rem_euclid(len - 1);
+		list.insert(new_idx as usize, item);
+	}
+
+	let zero_pos = list.iter().position(|(_, v)| *v == 0).unwrap();
+	[
+		list[(zero_pos + 1000).rem_euclid(original_list.len())].1,
+		list[(zero_pos + 2000).rem_euclid(original_list.len())].1,
+		list[(zero_pos + 3000).rem_euclid(original_list.len())].1,
+	]
+	.into_iter()
+	.sum()
+}
+
+fn part_2(file: &[i64]) -> i64 {
+	let original_list: Vec<i64> = file.iter().map(|x| x * 811589153).collect();
+
+	let len = original_list.len() as i64;
+	let mut list: Vec<_> = original_list.iter().copied().enumerate().collect();
+
+	for _ in 0..10 {
+		for (idx, _) in original_list.iter().enumerate() {
+			let item_idx = list
+				.iter()
+				.position(|(original_idx, _)| *original_idx == idx)
+				.unwrap();
+			let item = list.remove(item_idx);
+			let new_idx = (item_idx as i64 + item.1).rem_euclid(len - 1);
+			list.insert(new_idx as usize, item);
+		}
+	}
+
+	let zero_pos = list.iter().position(|(_, v)| *v == 0).unwrap();
+	[
+		list[(zero_pos + 1000).rem_euclid(original_list.len())].1,
+		list[(zero_pos + 2000).rem_euclid(original_list.len())].1,
+		list[(zero_pos + 3000).rem_euclid(original_list.len())].1,
+	]
+	.into_iter()
+	.sum()
+}
+
+#[cfg(test)]
+mod tests {
+	static EXAMPLE: &str = "1
+2
+-3
+3
+-2
+0
+4
+";
+
+	#[test]
+	fn part_1_example_1() {
+		assert_eq!(super::part_1(&super::parse_input(EXAMPLE).unwrap()), 3);
+	}
+
+	#[test]
+	fn part_2_example_1() {
+		assert_eq!(
+			super::part_2(&super::parse_input(EXAMPLE).unwrap()),
+			1623178306
+		);
+	}
+}
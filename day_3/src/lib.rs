@@ -0,0 +1,78 @@
+use anyhow::{anyhow, bail};
+use aoc_common::Solution;
+use itertools::Itertools;
+use std::fmt::Display;
+
+pub struct Day;
+
+impl Solution for Day {
+	const DAY: u32 = 3;
+
+	type Input = Vec<Vec<u8>>;
+
+	fn parse(input: &str) -> anyhow::Result<Vec<Vec<u8>>> {
+		parse_input(input)
+	}
+
+	fn part_1(rucksacks: &Vec<Vec<u8>>) -> anyhow::Result<impl Display> {
+		part_1(rucksacks)
+	}
+
+	fn part_2(rucksacks: &Vec<Vec<u8>>) -> anyhow::Result<impl Display> {
+		part_2(rucksacks)
+	}
+}
+
+/// The items in each rucksack, as letters.
+fn parse_input(input: &str) -> anyhow::Result<Vec<Vec<u8>>> {
+	input
+		.lines()
+		.map(|line| {
+			if !line.bytes().all(|item| item.is_ascii_alphabetic()) {
+				bail!("expected the items of a rucksack, got `{}`", line);
+			}
+			Ok(line.as_bytes().to_vec())
+		})
+		.collect()
+}
+
+fn part_1(rucksacks: &[Vec<u8>]) -> anyhow::Result<u64> {
+	let mut sum_of_priorities: u64 = 0;
+
+	for line in rucksacks {
+		let first_compartment = &line[..line.len() / 2];
+		let second_compartment = &line[line.len() / 2..];
+
+		let shared_item = first_compartment
+			.iter()
+			.find(|x| second_compartment.contains(x))
+			.ok_or_else(|| anyhow!("invalid input"))?;
+
+		if shared_item.is_ascii_lowercase() {
+			sum_of_priorities += (*shared_item - b'a' + 1) as u64;
+		} else if shared_item.is_ascii_uppercase() {
+			sum_of_priorities += (*shared_item - b'A' + 27) as u64;
+		}
+	}
+
+	Ok(sum_of_priorities)
+}
+
+fn part_2(rucksacks: &[Vec<u8>]) -> anyhow::Result<u64> {
+	let mut sum_of_priorities: u64 = 0;
+
+	for (a, b, c) in rucksacks.iter().tuples() {
+		let shared_item = a
+			.iter()
+			.find(|a_elem| b.contains(a_elem) && c.contains(a_elem))
+			.ok_or_else(|| anyhow!("didn't find a common item"))?;
+
+		if shared_item.is_ascii_lowercase() {
+			sum_of_priorities += (*shared_item - b'a' + 1) as u64;
+		} else if shared_item.is_ascii_uppercase() {
+			sum_of_priorities += (*shared_item - b'A' + 27) as u64;
+		}
+	}
+
+	Ok(sum_of_priorities)
+}
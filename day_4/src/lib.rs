@@ -0,0 +1,98 @@
+#![allow(clippy::type_complexity)]
+
+use anyhow::anyhow;
+use aoc_common::Solution;
+use nom::character::complete::multispace0;
+use nom::combinator::map;
+use nom::multi::many1;
+use nom::sequence::{separated_pair, terminated};
+use nom::IResult;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+pub struct Day;
+
+impl Solution for Day {
+	const DAY: u32 = 4;
+
+	type Input = Vec<(RangeInclusive<u64>, RangeInclusive<u64>)>;
+
+	fn parse(input: &str) -> anyhow::Result<Vec<(RangeInclusive<u64>, RangeInclusive<u64>)>> {
+		parse_input(input)
+	}
+
+	fn part_1(
+		pairs: &Vec<(RangeInclusive<u64>, RangeInclusive<u64>)>,
+	) -> anyhow::Result<impl Display> {
+		Ok(part_1(pairs))
+	}
+
+	fn part_2(
+		pairs: &Vec<(RangeInclusive<u64>, RangeInclusive<u64>)>,
+	) -> anyhow::Result<impl Display> {
+		Ok(part_2(pairs))
+	}
+}
+
+fn part_1(pairs: &[(RangeInclusive<u64>, RangeInclusive<u64>)]) -> usize {
+	pairs
+		.iter()
+		.filter(|(a, b)| {
+			(a.start() >= b.start() && a.end() <= b.end())
+				|| (b.start() >= a.start() && b.end() <= a.end())
+		})
+		.count()
+}
+
+fn part_2(pairs: &[(RangeInclusive<u64>, RangeInclusive<u64>)]) -> usize {
+	pairs
+		.iter()
+		.filter(|(a, b)| a.clone().any(|a| b.contains(&a)))
+		.count()
+}
+
+fn parse_input(input: &str) -> anyhow::Result<Vec<(RangeInclusive<u64>, RangeInclusive<u64>)>> {
+	parse_pairs(input)
+		.map_err(|err| anyhow!("{err:?}"))
+		.map(|(_, pairs)| pairs)
+}
+
+fn parse_pairs(input: &str) -> IResult<&str, Vec<(RangeInclusive<u64>, RangeInclusive<u64>)>> {
+	use nom::character::complete::{char, u64};
+	many1(terminated(
+		map(
+			separated_pair(
+				separated_pair(u64, char('-'), u64),
+				char(','),
+				separated_pair(u64, char('-'), u64),
+			),
+			|((a_start, a_end), (b_start, b_end))| (a_start..=a_end, b_start..=b_end),
+		),
+		multispace0,
+	))(input)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{parse_input, part_1, part_2};
+
+	const EXAMPLE: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+";
+
+	#[test]
+	fn part_1_example() {
+		let p1 = part_1(&parse_input(EXAMPLE).unwrap());
+		assert_eq!(p1, 2);
+	}
+
+	#[test]
+	fn part_2_example() {
+		let p2 = part_2(&parse_input(EXAMPLE).unwrap());
+		assert_eq!(p2, 4);
+	}
+}
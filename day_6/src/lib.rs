@@ -0,0 +1,73 @@
+use anyhow::{anyhow, bail};
+use aoc_common::Solution;
+use itertools::Itertools;
+use std::fmt::Display;
+
+pub struct Day;
+
+impl Solution for Day {
+	const DAY: u32 = 6;
+
+	type Input = Vec<u8>;
+
+	fn parse(input: &str) -> anyhow::Result<Vec<u8>> {
+		parse_input(input)
+	}
+
+	fn part_1(datastream: &Vec<u8>) -> anyhow::Result<impl Display> {
+		part_1(datastream)
+	}
+
+	fn part_2(datastream: &Vec<u8>) -> anyhow::Result<impl Display> {
+		part_2(datastream)
+	}
+}
+
+fn parse_input(input: &str) -> anyhow::Result<Vec<u8>> {
+	let datastream = input.trim();
+	if !datastream.bytes().all(|c| c.is_ascii_lowercase()) {
+		bail!("expected a datastream of letters, got `{}`", datastream);
+	}
+	Ok(datastream.as_bytes().to_vec())
+}
+
+fn part_1(datastream: &[u8]) -> anyhow::Result<usize> {
+	datastream
+		.windows(4)
+		.enumerate()
+		.find(|(_, window)| window.iter().duplicates().next().is_none())
+		.ok_or_else(|| anyhow!("not found"))
+		.map(|(idx, window)| idx + window.len())
+}
+
+fn part_2(datastream: &[u8]) -> anyhow::Result<usize> {
+	datastream
+		.windows(14)
+		.enumerate()
+		.find(|(_, window)| window.iter().duplicates().next().is_none())
+		.ok_or_else(|| anyhow!("not found"))
+		.map(|(idx, window)| idx + window.len())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{part_1, part_2};
+
+	#[test]
+	fn part_1_example() {
+		assert_eq!(part_1(b"mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap(), 7);
+		assert_eq!(part_1(b"bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap(), 5);
+		assert_eq!(part_1(b"nppdvjthqldpwncqszvftbrmjlhg").unwrap(), 6);
+		assert_eq!(part_1(b"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap(), 10);
+		assert_eq!(part_1(b"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap(), 11);
+	}
+
+	#[test]
+	fn part_2_example() {
+		assert_eq!(part_2(b"mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap(), 19);
+		assert_eq!(part_2(b"bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap(), 23);
+		assert_eq!(part_2(b"nppdvjthqldpwncqszvftbrmjlhg").unwrap(), 23);
+		assert_eq!(part_2(b"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap(), 29);
+		assert_eq!(part_2(b"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap(), 26);
+	}
+}
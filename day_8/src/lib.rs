@@ -0,0 +1,85 @@
+use anyhow::anyhow;
+use aoc_common::grid::{Grid, DIRECTIONS_4};
+use aoc_common::Solution;
+use std::fmt::Display;
+
+pub struct Day;
+
+impl Solution for Day {
+	const DAY: u32 = 8;
+
+	type Input = Grid<u8>;
+
+	fn parse(input: &str) -> anyhow::Result<Grid<u8>> {
+		parse_input(input)
+	}
+
+	fn part_1(trees: &Grid<u8>) -> anyhow::Result<impl Display> {
+		Ok(part_1(trees))
+	}
+
+	fn part_2(trees: &Grid<u8>) -> anyhow::Result<impl Display> {
+		Ok(part_2(trees))
+	}
+}
+
+fn parse_input(input: &str) -> anyhow::Result<Grid<u8>> {
+	Grid::parse(input, |c| {
+		c.to_digit(10)
+			.map(|height| height as u8)
+			.ok_or_else(|| anyhow!("expected the height of a tree, got `{}`", c))
+	})
+}
+
+fn part_1(trees: &Grid<u8>) -> usize {
+	trees
+		.iter()
+		.filter(|(pos, current)| {
+			DIRECTIONS_4
+				.into_iter()
+				.any(|direction| trees.ray(*pos, direction).all(|(_, item)| item < *current))
+		})
+		.count()
+}
+
+fn part_2(trees: &Grid<u8>) -> usize {
+	trees
+		.iter()
+		.map(|(pos, current)| {
+			DIRECTIONS_4
+				.into_iter()
+				.map(|direction| {
+					let mut seen_trees = 0;
+					for (_, item) in trees.ray(pos, direction) {
+						seen_trees += 1;
+						if item >= current {
+							break;
+						}
+					}
+					seen_trees
+				})
+				.product()
+		})
+		.max()
+		.unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+	const EXAMPLE: &str = "30373
+25512
+65332
+33549
+35390
+";
+
+	#[test]
+	fn part_1_example_1() {
+		assert_eq!(super::part_1(&super::parse_input(EXAMPLE).unwrap()), 21)
+	}
+
+	#[test]
+	fn part_2_example_1() {
+		assert_eq!(super::part_2(&super::parse_input(EXAMPLE).unwrap()), 8);
+	}
+}